@@ -1,5 +1,6 @@
 import { resolve } from 'path';
 import { readFileSync } from 'fs';
+import { createHash, createPublicKey } from 'crypto';
 import { config } from 'dotenv';
 import jwt from 'jsonwebtoken';
 import { defineConfig, externalizeDepsPlugin } from 'electron-vite';
@@ -10,10 +11,10 @@ import tailwindcss from '@tailwindcss/vite';
 // Only the signed JWT is embedded — the private key never ships in the binary.
 config();
 
-function generateDeveloperToken(): string {
-  const teamId = process.env.APPLE_TEAM_ID;
-  const keyId = process.env.APPLE_KEY_ID;
+// Number of SHA-256 bytes kept in the signing key fingerprint
+const FINGERPRINT_BYTES = 16;
 
+function loadPrivateKey(): string {
   let privateKey = process.env.APPLE_PRIVATE_KEY ?? '';
   if (!privateKey && process.env.APPLE_PRIVATE_KEY_PATH) {
     try {
@@ -22,6 +23,23 @@ function generateDeveloperToken(): string {
       // Key file not found — token will be empty
     }
   }
+  return privateKey;
+}
+
+// SHA-256 of the DER (SPKI) public key — identifies the .p8 without exposing it
+function signingKeyFingerprint(privateKey: string): string {
+  if (!privateKey) return '';
+  try {
+    const der = createPublicKey(privateKey).export({ type: 'spki', format: 'der' });
+    return createHash('sha256').update(der).digest('hex').slice(0, FINGERPRINT_BYTES * 2);
+  } catch {
+    return '';
+  }
+}
+
+function generateDeveloperToken(privateKey: string): string {
+  const teamId = process.env.APPLE_TEAM_ID;
+  const keyId = process.env.APPLE_KEY_ID;
 
   if (!teamId || !keyId || !privateKey) {
     console.warn('[build] Missing Apple credentials — developer token will be empty');
@@ -41,13 +59,16 @@ function generateDeveloperToken(): string {
   );
 }
 
-const developerToken = generateDeveloperToken();
+const privateKey = loadPrivateKey();
+const developerToken = generateDeveloperToken(privateKey);
+const keyFingerprint = developerToken ? signingKeyFingerprint(privateKey) : '';
 
 export default defineConfig({
   main: {
     plugins: [externalizeDepsPlugin()],
     define: {
       __APPLE_DEVELOPER_TOKEN__: JSON.stringify(developerToken),
+      __APPLE_KEY_FINGERPRINT__: JSON.stringify(keyFingerprint),
     },
     build: {
       rollupOptions: {
@@ -0,0 +1,72 @@
+// Configurable Apple Music API base URL.
+// Lets development builds point catalog/test requests at a mock or proxy.
+//
+// The main process makes no catalog requests of its own yet: there is no
+// metadata prefetch or motion-artwork fetch here, and the renderer's catalog
+// calls go through MusicKit JS, which always talks to Apple. The override
+// covers testDeveloperToken and the startup preconnect. Any main-process
+// API fetch added later should build its URL with buildApiUrl(getApiBaseUrl(), …).
+
+import { net, session } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import { getDeveloperToken } from './token';
+
+export const DEFAULT_API_BASE_URL = 'https://api.music.apple.com';
+export const ARTWORK_CDN_URL = 'https://is1-ssl.mzstatic.com';
+
+const LOCAL_HOSTS = ['localhost', '127.0.0.1', '[::1]'];
+
+/** Normalizes and validates a base URL: https anywhere, http only for localhost. */
+export function validateApiBaseUrl(url: string): string {
+  let parsed: URL;
+  try {
+    parsed = new URL(url.trim());
+  } catch {
+    throw new Error(`Invalid API base URL: ${url}`);
+  }
+
+  const isLocal = LOCAL_HOSTS.includes(parsed.hostname);
+  if (parsed.protocol !== 'https:' && !(parsed.protocol === 'http:' && isLocal)) {
+    throw new Error('API base URL must use https (http is only allowed for localhost)');
+  }
+
+  return `${parsed.origin}${parsed.pathname}`.replace(/\/+$/, '');
+}
+
+export function buildApiUrl(baseUrl: string, path: string): string {
+  return `${baseUrl.replace(/\/+$/, '')}/${path.replace(/^\/+/, '')}`;
+}
+
+export function getApiBaseUrl(): string {
+  return getStoreValue<string>('apiBaseUrl') ?? DEFAULT_API_BASE_URL;
+}
+
+export function setApiBaseUrl(url: string): string {
+  const normalized = validateApiBaseUrl(url);
+  setStoreValue('apiBaseUrl', normalized);
+  console.log(`[TUFFAHI] API base URL set to ${normalized}`);
+  return normalized;
+}
+
+/** Verifies the developer token against the configured API's /v1/test endpoint. */
+export async function testDeveloperToken(): Promise<boolean> {
+  const response = await net.fetch(buildApiUrl(getApiBaseUrl(), '/v1/test'), {
+    headers: { Authorization: `Bearer ${await getDeveloperToken()}` },
+  });
+  return response.ok;
+}
+
+/**
+ * Opens connections to the API and artwork CDN ahead of the first request so
+ * it doesn't pay the TLS handshake. Uses the default session's pool, which
+ * net.fetch and the renderer share. Best-effort: failures are ignored.
+ */
+export function prewarmConnections(): void {
+  for (const url of [getApiBaseUrl(), ARTWORK_CDN_URL]) {
+    try {
+      session.defaultSession.preconnect({ url, numSockets: 2 });
+    } catch (error) {
+      console.warn(`[TUFFAHI] Could not prewarm ${url}:`, error);
+    }
+  }
+}
@@ -0,0 +1,47 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({
+  app: { getPath: () => '/tmp' },
+  nativeImage: {},
+  net: {},
+  powerMonitor: { on: vi.fn() },
+}));
+vi.mock('./store', () => ({
+  getStoreValue: () => undefined,
+  setStoreValue: () => {},
+}));
+vi.mock('./events', () => ({ emitToAll: vi.fn() }));
+
+import { IndexEntry, reconcileIndex, selectEvictions } from './artwork-cache';
+
+function entries(list: [string, number, number][]): Map<string, IndexEntry> {
+  return new Map(list.map(([name, size, mtimeMs]) => [name, { size, mtimeMs }]));
+}
+
+describe('selectEvictions', () => {
+  it('evicts nothing when the cache fits', () => {
+    expect(selectEvictions(entries([['a', 50, 1], ['b', 50, 2]]), 100)).toEqual([]);
+  });
+
+  it('evicts the oldest files until the cache fits', () => {
+    const cache = entries([['new', 40, 30], ['old', 40, 10], ['mid', 40, 20]]);
+    expect(selectEvictions(cache, 80)).toEqual(['old']);
+    expect(selectEvictions(cache, 50)).toEqual(['old', 'mid']);
+  });
+
+  it('evicts everything for a zero cap', () => {
+    expect(selectEvictions(entries([['a', 1, 1], ['b', 1, 2]]), 0)).toEqual(['a', 'b']);
+  });
+});
+
+describe('reconcileIndex', () => {
+  it('counts entries missing from disk and new or changed files', () => {
+    const stale = entries([['gone', 10, 1], ['same', 10, 1], ['resized', 10, 1]]);
+    const scanned = entries([['same', 10, 1], ['resized', 20, 1], ['new', 5, 2]]);
+
+    const result = reconcileIndex(stale, scanned);
+    expect(result.removed).toBe(1);
+    expect(result.reindexed).toBe(2);
+    expect([...result.index.keys()]).toEqual(['same', 'resized', 'new']);
+  });
+});
@@ -0,0 +1,298 @@
+// On-disk artwork cache for the main process (tray, notifications, recap).
+// Files live under userData/artwork-cache, named by a hash of the URL; an
+// in-memory index tracks sizes/mtimes for LRU eviction under a size cap.
+// Requests without an explicit size use the artwork quality preference.
+
+import { app, nativeImage, net, powerMonitor } from 'electron';
+import { createHash } from 'crypto';
+import { join, extname } from 'path';
+import { copyFileSync, existsSync, mkdirSync, readdirSync, statSync, unlinkSync, utimesSync, writeFileSync } from 'fs';
+import { getStoreValue, setStoreValue } from './store';
+import { emitToAll } from './events';
+import { AppError } from './errors';
+
+export interface IndexEntry {
+  size: number;
+  mtimeMs: number;
+}
+
+export interface RepairSummary {
+  removed: number;
+  reindexed: number;
+  total_bytes: number;
+}
+
+export const MAX_ARTWORK_CACHE_BYTES = 200 * 1024 * 1024;
+
+// Fallback image copy lives next to cached art but is never indexed/evicted
+const FALLBACK_PREFIX = 'fallback';
+
+// file name → entry
+let index = new Map<string, IndexEntry>();
+let indexLoaded = false;
+
+export function artworkCacheDir(): string {
+  const dir = join(app.getPath('userData'), 'artwork-cache');
+  if (!existsSync(dir)) mkdirSync(dir, { recursive: true });
+  return dir;
+}
+
+export function fileNameForUrl(url: string): string {
+  const ext = extname(new URL(url).pathname).toLowerCase();
+  const safeExt = ['.jpg', '.jpeg', '.png', '.webp'].includes(ext) ? ext : '.jpg';
+  return createHash('sha256').update(url).digest('hex').slice(0, 32) + safeExt;
+}
+
+export function totalBytes(entries: Map<string, IndexEntry>): number {
+  let total = 0;
+  for (const entry of entries.values()) total += entry.size;
+  return total;
+}
+
+/** Oldest-first (by mtime) file names to evict until the total fits in maxBytes. */
+export function selectEvictions(entries: Map<string, IndexEntry>, maxBytes: number): string[] {
+  let total = totalBytes(entries);
+  if (total <= maxBytes) return [];
+
+  const evict: string[] = [];
+  const byAge = [...entries.entries()].sort((a, b) => a[1].mtimeMs - b[1].mtimeMs);
+  for (const [name, entry] of byAge) {
+    if (total <= maxBytes) break;
+    evict.push(name);
+    total -= entry.size;
+  }
+  return evict;
+}
+
+/** Rebuilds an index from a directory scan, counting stale and new/changed entries. */
+export function reconcileIndex(
+  stale: Map<string, IndexEntry>,
+  scanned: Map<string, IndexEntry>,
+): { index: Map<string, IndexEntry>; removed: number; reindexed: number } {
+  let removed = 0;
+  for (const name of stale.keys()) {
+    if (!scanned.has(name)) removed++;
+  }
+
+  let reindexed = 0;
+  for (const [name, entry] of scanned) {
+    const previous = stale.get(name);
+    if (!previous || previous.size !== entry.size || previous.mtimeMs !== entry.mtimeMs) {
+      reindexed++;
+    }
+  }
+
+  return { index: new Map(scanned), removed, reindexed };
+}
+
+function scanDirectory(dir: string): Map<string, IndexEntry> {
+  const scanned = new Map<string, IndexEntry>();
+  for (const name of readdirSync(dir)) {
+    if (name.startsWith(FALLBACK_PREFIX)) continue;
+    try {
+      const stat = statSync(join(dir, name));
+      if (stat.isFile()) {
+        scanned.set(name, { size: stat.size, mtimeMs: stat.mtimeMs });
+      }
+    } catch {
+      // Vanished between readdir and stat
+    }
+  }
+  return scanned;
+}
+
+function evict(dir: string, names: string[]): void {
+  for (const name of names) {
+    try {
+      unlinkSync(join(dir, name));
+    } catch {
+      // Already gone
+    }
+    index.delete(name);
+  }
+}
+
+/** For files deleted by someone else (the global cache budget). */
+export function dropFromArtworkIndex(names: string[]): void {
+  for (const name of names) index.delete(name);
+}
+
+function ensureIndex(): void {
+  if (indexLoaded) return;
+  index = scanDirectory(artworkCacheDir());
+  indexLoaded = true;
+}
+
+export function enforceArtworkCacheCap(maxBytes: number = MAX_ARTWORK_CACHE_BYTES): number {
+  ensureIndex();
+  const names = selectEvictions(index, maxBytes);
+  evict(artworkCacheDir(), names);
+  return names.length;
+}
+
+export function repairArtworkCache(maxBytes: number = MAX_ARTWORK_CACHE_BYTES): RepairSummary {
+  const dir = artworkCacheDir();
+  const result = reconcileIndex(index, scanDirectory(dir));
+  index = result.index;
+  indexLoaded = true;
+
+  const evicted = enforceArtworkCacheCap(maxBytes);
+  const summary = {
+    removed: result.removed + evicted,
+    reindexed: result.reindexed,
+    total_bytes: totalBytes(index),
+  };
+  console.log('[TUFFAHI] Artwork cache repaired:', summary);
+  return summary;
+}
+
+async function downloadArtwork(url: string): Promise<string> {
+  ensureIndex();
+  const dir = artworkCacheDir();
+  const name = fileNameForUrl(url);
+  const path = join(dir, name);
+
+  if (existsSync(path)) {
+    // Touch so LRU eviction sees the hit
+    const now = new Date();
+    try {
+      utimesSync(path, now, now);
+    } catch {
+      // Non-fatal
+    }
+    const size = index.get(name)?.size ?? statSync(path).size;
+    index.set(name, { size, mtimeMs: now.getTime() });
+    return path;
+  }
+
+  const response = await net.fetch(url);
+  if (!response.ok) {
+    throw new Error(`Artwork download failed (${response.status}): ${url}`);
+  }
+  const body = Buffer.from(await response.arrayBuffer());
+  writeFileSync(path, body);
+  index.set(name, { size: body.length, mtimeMs: Date.now() });
+  enforceArtworkCacheCap();
+  return path;
+}
+
+// ── Fallback artwork ────────────────────────────────────────────────────────
+
+export function getFallbackArtwork(): string | null {
+  const path = getStoreValue<string>('fallbackArtworkPath') ?? null;
+  return path && existsSync(path) ? path : null;
+}
+
+export function setFallbackArtwork(path: string | null): string | null {
+  if (path === null) {
+    setStoreValue('fallbackArtworkPath', null);
+    return null;
+  }
+
+  if (!existsSync(path) || nativeImage.createFromPath(path).isEmpty()) {
+    throw new Error(`Fallback artwork is not a readable image: ${path}`);
+  }
+
+  // Keep our own copy so moving/deleting the original doesn't break it
+  const cached = join(artworkCacheDir(), FALLBACK_PREFIX + (extname(path).toLowerCase() || '.png'));
+  copyFileSync(path, cached);
+  setStoreValue('fallbackArtworkPath', cached);
+  return cached;
+}
+
+/** Runs the download, substituting the fallback image for a missing URL or a failure. */
+export async function withFallback(
+  url: string | null | undefined,
+  download: (url: string) => Promise<string>,
+  fallback: string | null,
+): Promise<string> {
+  if (!url) {
+    if (fallback) return fallback;
+    throw new Error('No artwork URL and no fallback artwork configured');
+  }
+
+  try {
+    return await download(url);
+  } catch (error) {
+    if (fallback) {
+      console.warn('[TUFFAHI] Artwork download failed, using fallback:', error);
+      return fallback;
+    }
+    throw error;
+  }
+}
+
+export async function getCachedArtwork(url: string | null | undefined, size?: number): Promise<string> {
+  return withFallback(url && resolveArtworkUrl(url, size), downloadArtwork, getFallbackArtwork());
+}
+
+// ── Artwork quality ─────────────────────────────────────────────────────────
+
+export type ArtworkQuality = 'low' | 'medium' | 'high';
+
+const QUALITIES: ArtworkQuality[] = ['low', 'medium', 'high'];
+
+// Square edge length in pixels
+export const ARTWORK_QUALITY_DIMENSIONS: Record<ArtworkQuality, number> = {
+  low: 300,
+  medium: 600,
+  high: 1200,
+};
+
+// Reported by the renderer (navigator.connection.saveData)
+let networkMetered = false;
+
+export function getArtworkQuality(): ArtworkQuality {
+  const stored = getStoreValue<ArtworkQuality>('artworkQuality');
+  return stored && QUALITIES.includes(stored) ? stored : 'high';
+}
+
+export function setArtworkQuality(quality: string): ArtworkQuality {
+  if (!QUALITIES.includes(quality as ArtworkQuality)) {
+    throw new AppError('INVALID_ARGUMENT', `Invalid artwork quality: ${quality} (expected ${QUALITIES.join(', ')})`, {
+      quality,
+    });
+  }
+  setStoreValue('artworkQuality', quality);
+  emitToAll('artwork-quality-changed', getEffectiveArtworkQuality());
+  return quality as ArtworkQuality;
+}
+
+export function setNetworkMetered(metered: boolean): void {
+  if (networkMetered === metered) return;
+  networkMetered = metered;
+  emitToAll('artwork-quality-changed', getEffectiveArtworkQuality());
+}
+
+/** Metered connections drop to low; battery power caps at medium. Never raises the preference. */
+export function autoLowerQuality(
+  preferred: ArtworkQuality,
+  conditions: { onBattery: boolean; metered: boolean },
+): ArtworkQuality {
+  const cap: ArtworkQuality = conditions.metered ? 'low' : conditions.onBattery ? 'medium' : 'high';
+  return QUALITIES[Math.min(QUALITIES.indexOf(preferred), QUALITIES.indexOf(cap))];
+}
+
+export function getEffectiveArtworkQuality(): ArtworkQuality {
+  return autoLowerQuality(getArtworkQuality(), {
+    onBattery: powerMonitor.isOnBatteryPower(),
+    metered: networkMetered,
+  });
+}
+
+export function artworkDimension(quality: ArtworkQuality): number {
+  return ARTWORK_QUALITY_DIMENSIONS[quality];
+}
+
+/**
+ * Fills in the size of an Apple artwork URL: either a `{w}x{h}` template
+ * or an already-sized `.../600x600bb.jpg`. Uses `size` when given,
+ * otherwise the effective quality. Other URLs pass through unchanged.
+ */
+export function resolveArtworkUrl(url: string, size?: number): string {
+  const edge = size ?? artworkDimension(getEffectiveArtworkQuality());
+  if (url.includes('{w}') || url.includes('{h}')) {
+    return url.replace('{w}', String(edge)).replace('{h}', String(edge));
+  }
+  return url.replace(/\/\d+x\d+(bb|cc|sr)?\.(jpg|jpeg|png|webp)$/i, `/${edge}x${edge}$1.$2`);
+}
@@ -0,0 +1,51 @@
+// Audio interruptions: pause when something takes over the system, and
+// resume afterwards if we were playing.
+//
+// Electron has no AVAudioSession bridge, so call interruptions aren't
+// observable here. The only interruption we handle is system
+// suspend/resume, via powerMonitor. Screen lock deliberately keeps
+// playing, and other sources are a no-op.
+
+import { powerMonitor } from 'electron';
+import { emitToAll } from './events';
+import { getPlayerState } from './player-state';
+
+export interface AudioInterruption {
+  began: boolean;
+  should_resume: boolean;
+}
+
+export type InterruptionNotice = 'began' | 'ended';
+
+/** Maps a begin/end notice to the event payload. Only an end after playback was interrupted resumes. */
+export function mapInterruption(notice: InterruptionNotice, wasPlaying: boolean): AudioInterruption {
+  return notice === 'began'
+    ? { began: true, should_resume: false }
+    : { began: false, should_resume: wasPlaying };
+}
+
+let initialized = false;
+let interrupted = false;
+let wasPlaying = false;
+
+function begin(): void {
+  if (interrupted) return;
+  interrupted = true;
+  wasPlaying = getPlayerState().isPlaying;
+  emitToAll('audio-interruption', mapInterruption('began', wasPlaying));
+}
+
+function end(): void {
+  if (!interrupted) return;
+  interrupted = false;
+  emitToAll('audio-interruption', mapInterruption('ended', wasPlaying));
+  wasPlaying = false;
+}
+
+export function initAudioInterruptions(): void {
+  if (initialized) return;
+  initialized = true;
+
+  powerMonitor.on('suspend', begin);
+  powerMonitor.on('resume', end);
+}
@@ -1,15 +1,17 @@
 import { BrowserWindow } from 'electron';
 import { getDeveloperToken } from './token';
+import { registerWindow } from './windows';
+import { getBranding } from './branding';
 
 let authWindow: BrowserWindow | null = null;
 
-export function openAuthWindow(mainWindow: BrowserWindow, authUrl: string): void {
+export async function openAuthWindow(mainWindow: BrowserWindow, authUrl: string): Promise<void> {
+  const developerToken = await getDeveloperToken();
+
   if (authWindow && !authWindow.isDestroyed()) {
     authWindow.close();
   }
 
-  const developerToken = getDeveloperToken();
-
   authWindow = new BrowserWindow({
     width: 600,
     height: 700,
@@ -22,6 +24,8 @@ export function openAuthWindow(mainWindow: BrowserWindow, authUrl: string): void
     },
   });
 
+  registerWindow('auth', authWindow);
+
   const initScript = `
     (function() {
       if (window.__tuffahi_injected) return;
@@ -30,6 +34,7 @@ export function openAuthWindow(mainWindow: BrowserWindow, authUrl: string): void
       console.log('[TUFFAHI AUTH] Injected on: ' + window.location.href);
 
       var DEVELOPER_TOKEN = '${developerToken}';
+      var APP_NAME = ${JSON.stringify(getBranding().appName)};
       var relayed = false;
 
       function relayToken(token) {
@@ -61,7 +66,7 @@ export function openAuthWindow(mainWindow: BrowserWindow, authUrl: string): void
               var thirdPartyData = JSON.stringify({
                 developerToken: DEVELOPER_TOKEN,
                 thirdPartyIconURL: '',
-                thirdPartyName: 'Tuffahi',
+                thirdPartyName: APP_NAME,
                 thirdPartyToken: DEVELOPER_TOKEN
               });
               var response = {
@@ -0,0 +1,36 @@
+// "Continue playing similar music" when the queue runs out.
+// The main process owns the preference and decides when to ask for a seed;
+// fetching the station/recommendations stays in the renderer.
+
+import { getStoreValue, setStoreValue } from './store';
+import { emitToAll } from './events';
+
+export interface AutoplaySeedRequest {
+  last_track_id: string;
+}
+
+// Matches the renderer's settings default
+const DEFAULT_AUTOPLAY = true;
+
+export function getAutoplay(): boolean {
+  return getStoreValue<boolean>('autoplay') ?? DEFAULT_AUTOPLAY;
+}
+
+export function setAutoplay(enabled: boolean): boolean {
+  setStoreValue('autoplay', enabled);
+  emitToAll('autoplay-changed', enabled);
+  return enabled;
+}
+
+export function buildAutoplaySeed(enabled: boolean, lastTrackId: string | null): AutoplaySeedRequest | null {
+  if (!enabled || !lastTrackId) return null;
+  return { last_track_id: lastTrackId };
+}
+
+/** Called when the renderer's queue finishes. Returns whether a seed was requested. */
+export function reportQueueEnded(lastTrackId: string | null): boolean {
+  const seed = buildAutoplaySeed(getAutoplay(), lastTrackId);
+  if (!seed) return false;
+  emitToAll('request-autoplay-seed', seed);
+  return true;
+}
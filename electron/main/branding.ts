@@ -0,0 +1,63 @@
+// White-label branding overrides, read from the `branding` store namespace.
+// Any missing or invalid value falls back to the stock Tuffahi defaults.
+
+import { existsSync } from 'fs';
+import { extname } from 'path';
+import { getStoreValue } from './store';
+
+export interface Branding {
+  appName: string;
+  accentColor: string;
+  trayTooltip: string;
+  iconPath: string | null;
+}
+
+export const DEFAULT_BRANDING: Branding = {
+  appName: 'Tuffahi',
+  accentColor: '#0D9488',
+  trayTooltip: 'Tuffahi',
+  iconPath: null,
+};
+
+const HEX_COLOR = /^#(?:[0-9a-f]{3}|[0-9a-f]{6})$/i;
+const ICON_EXTENSIONS = ['.png', '.jpg', '.jpeg', '.ico', '.icns'];
+
+export function isValidAccentColor(value: unknown): value is string {
+  return typeof value === 'string' && HEX_COLOR.test(value);
+}
+
+export function isValidIconPath(value: unknown): value is string {
+  return (
+    typeof value === 'string' &&
+    ICON_EXTENSIONS.includes(extname(value).toLowerCase()) &&
+    existsSync(value)
+  );
+}
+
+function nonEmpty(value: unknown): value is string {
+  return typeof value === 'string' && value.trim().length > 0;
+}
+
+export function resolveBranding(stored: Partial<Record<keyof Branding, unknown>> | undefined): Branding {
+  const { appName, accentColor, trayTooltip, iconPath } = stored ?? {};
+  const resolvedName = nonEmpty(appName) ? appName.trim() : DEFAULT_BRANDING.appName;
+
+  if (accentColor !== undefined && !isValidAccentColor(accentColor)) {
+    console.warn('[TUFFAHI] Ignoring invalid branding accentColor:', accentColor);
+  }
+  if (iconPath !== undefined && iconPath !== null && !isValidIconPath(iconPath)) {
+    console.warn('[TUFFAHI] Ignoring invalid branding iconPath:', iconPath);
+  }
+
+  return {
+    appName: resolvedName,
+    accentColor: isValidAccentColor(accentColor) ? accentColor : DEFAULT_BRANDING.accentColor,
+    // Tooltip follows the app name unless explicitly overridden
+    trayTooltip: nonEmpty(trayTooltip) ? trayTooltip.trim() : resolvedName,
+    iconPath: isValidIconPath(iconPath) ? iconPath : DEFAULT_BRANDING.iconPath,
+  };
+}
+
+export function getBranding(): Branding {
+  return resolveBranding(getStoreValue('branding'));
+}
@@ -0,0 +1,51 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({ app: { getPath: () => '/tmp' } }));
+vi.mock('./store', () => ({
+  getStoreValue: () => undefined,
+  setStoreValue: () => {},
+}));
+vi.mock('./artwork-cache', () => ({ dropFromArtworkIndex: vi.fn() }));
+
+import { CacheFile, selectCrossCacheEvictions, summarizeUsage } from './cache-budget';
+
+function file(name: string, size: number, mtimeMs: number, priority = 0, cache = 'artwork'): CacheFile {
+  return { cache, name, size, mtimeMs, priority };
+}
+
+describe('selectCrossCacheEvictions', () => {
+  it('evicts nothing within the budget', () => {
+    expect(selectCrossCacheEvictions([file('a', 50, 1), file('b', 50, 2)], 100)).toEqual([]);
+  });
+
+  it('evicts the oldest files first until the total fits', () => {
+    const files = [file('new', 40, 30), file('old', 40, 10), file('mid', 40, 20)];
+    expect(selectCrossCacheEvictions(files, 50).map((f) => f.name)).toEqual(['old', 'mid']);
+  });
+
+  it('empties lower-priority caches before touching older files elsewhere', () => {
+    const files = [file('kept', 40, 1, 1, 'other'), file('a', 40, 20, 0), file('b', 40, 30, 0)];
+    expect(selectCrossCacheEvictions(files, 40).map((f) => f.name)).toEqual(['a', 'b']);
+  });
+
+  it('does not reorder the input', () => {
+    const files = [file('new', 40, 30), file('old', 40, 10)];
+    selectCrossCacheEvictions(files, 0);
+    expect(files.map((f) => f.name)).toEqual(['new', 'old']);
+  });
+});
+
+describe('summarizeUsage', () => {
+  it('reports every known cache, even when empty', () => {
+    expect(summarizeUsage([], 100)).toEqual({ budget_bytes: 100, total_bytes: 0, caches: { artwork: 0 } });
+  });
+
+  it('sums sizes per cache and overall', () => {
+    const files = [file('a', 10, 1), file('b', 15, 2), file('c', 5, 3, 1, 'other')];
+    expect(summarizeUsage(files, 100)).toEqual({
+      budget_bytes: 100,
+      total_bytes: 30,
+      caches: { artwork: 25, other: 5 },
+    });
+  });
+});
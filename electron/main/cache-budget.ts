@@ -0,0 +1,132 @@
+// One size budget across every on-disk cache under userData.
+// Eviction goes by cache priority first — large, cheap-to-refetch media
+// before small metadata — then least-recently-used by mtime within the
+// same priority.
+//
+// Today the artwork cache is the only one on disk. Palettes are derived from
+// it and held in memory (capped in palette.ts), and there is no motion-artwork,
+// preview or metadata cache yet. A new on-disk cache joins the budget by
+// adding an entry to CACHES.
+
+import { app } from 'electron';
+import { join } from 'path';
+import { existsSync, readdirSync, statSync, unlinkSync } from 'fs';
+import { getStoreValue, setStoreValue } from './store';
+import { AppError } from './errors';
+import { dropFromArtworkIndex } from './artwork-cache';
+
+interface CacheDefinition {
+  name: string;
+  dir: string;
+  // Lower is evicted first
+  priority: number;
+}
+
+export const CACHES: readonly CacheDefinition[] = [{ name: 'artwork', dir: 'artwork-cache', priority: 0 }];
+
+export const DEFAULT_CACHE_BUDGET_BYTES = 500 * 1024 * 1024;
+export const MIN_CACHE_BUDGET_BYTES = 50 * 1024 * 1024;
+
+// Never evicted: the user's chosen fallback artwork
+const PINNED_PREFIX = 'fallback';
+
+export interface CacheFile {
+  cache: string;
+  name: string;
+  size: number;
+  mtimeMs: number;
+  priority: number;
+}
+
+export interface CacheUsage {
+  budget_bytes: number;
+  total_bytes: number;
+  caches: Record<string, number>;
+}
+
+function cachePath(dir: string): string {
+  return join(app.getPath('userData'), dir);
+}
+
+function scanCaches(): CacheFile[] {
+  const files: CacheFile[] = [];
+  for (const cache of CACHES) {
+    const dir = cachePath(cache.dir);
+    if (!existsSync(dir)) continue;
+    for (const name of readdirSync(dir)) {
+      if (name.startsWith(PINNED_PREFIX)) continue;
+      try {
+        const stat = statSync(join(dir, name));
+        if (stat.isFile()) {
+          files.push({ cache: cache.name, name, size: stat.size, mtimeMs: stat.mtimeMs, priority: cache.priority });
+        }
+      } catch {
+        // Vanished between readdir and stat
+      }
+    }
+  }
+  return files;
+}
+
+export function summarizeUsage(files: CacheFile[], budgetBytes: number): CacheUsage {
+  const caches: Record<string, number> = Object.fromEntries(CACHES.map((cache) => [cache.name, 0]));
+  let total = 0;
+  for (const file of files) {
+    caches[file.cache] = (caches[file.cache] ?? 0) + file.size;
+    total += file.size;
+  }
+  return { budget_bytes: budgetBytes, total_bytes: total, caches };
+}
+
+/** Files to delete, in order, until the total fits in the budget. */
+export function selectCrossCacheEvictions(files: CacheFile[], budgetBytes: number): CacheFile[] {
+  let total = files.reduce((sum, file) => sum + file.size, 0);
+  if (total <= budgetBytes) return [];
+
+  const ordered = [...files].sort((a, b) => a.priority - b.priority || a.mtimeMs - b.mtimeMs);
+  const evict: CacheFile[] = [];
+  for (const file of ordered) {
+    if (total <= budgetBytes) break;
+    evict.push(file);
+    total -= file.size;
+  }
+  return evict;
+}
+
+export function getCacheBudget(): number {
+  return getStoreValue<number>('cacheBudgetBytes') ?? DEFAULT_CACHE_BUDGET_BYTES;
+}
+
+export function getCacheUsage(): CacheUsage {
+  return summarizeUsage(scanCaches(), getCacheBudget());
+}
+
+export function enforceCacheBudget(): CacheUsage {
+  const budget = getCacheBudget();
+  const files = scanCaches();
+  const evictions = selectCrossCacheEvictions(files, budget);
+
+  const dirs = new Map(CACHES.map((cache) => [cache.name, cachePath(cache.dir)]));
+  for (const file of evictions) {
+    try {
+      unlinkSync(join(dirs.get(file.cache)!, file.name));
+    } catch {
+      // Already gone
+    }
+  }
+  dropFromArtworkIndex(evictions.filter((file) => file.cache === 'artwork').map((file) => file.name));
+
+  if (evictions.length > 0) {
+    console.log(`[TUFFAHI] Cache budget: evicted ${evictions.length} files`);
+  }
+  const evicted = new Set(evictions);
+  return summarizeUsage(files.filter((file) => !evicted.has(file)), budget);
+}
+
+export function setCacheBudget(bytes: number): CacheUsage {
+  if (!Number.isInteger(bytes) || bytes < MIN_CACHE_BUDGET_BYTES) {
+    throw new AppError('INVALID_ARGUMENT', `Cache budget must be at least ${MIN_CACHE_BUDGET_BYTES} bytes`, { bytes });
+  }
+  setStoreValue('cacheBudgetBytes', bytes);
+  return enforceCacheBudget();
+}
@@ -0,0 +1,55 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({ powerMonitor: { on: vi.fn(), removeListener: vi.fn() } }));
+vi.mock('./events', () => ({ emitToAll: vi.fn() }));
+vi.mock('./token', () => ({ refreshDeveloperToken: vi.fn() }));
+
+import { CLOCK_JUMP_THRESHOLD_MS, ClockJumpDetector } from './clock-watch';
+
+describe('ClockJumpDetector', () => {
+  it('has nothing to compare on the first sample', () => {
+    expect(new ClockJumpDetector().sample({ monotonicMs: 0, wallMs: 1_000_000 })).toBeNull();
+  });
+
+  it('ignores clocks that advance together, with some jitter', () => {
+    const detector = new ClockJumpDetector();
+    detector.sample({ monotonicMs: 0, wallMs: 1_000_000 });
+    expect(detector.sample({ monotonicMs: 30_000, wallMs: 1_030_000 })).toBeNull();
+    expect(detector.sample({ monotonicMs: 60_000, wallMs: 1_060_500 })).toBeNull();
+  });
+
+  it('reports forward and backward jumps as signed drift', () => {
+    const detector = new ClockJumpDetector();
+    detector.sample({ monotonicMs: 0, wallMs: 1_000_000 });
+    expect(detector.sample({ monotonicMs: 30_000, wallMs: 1_030_000 + 3_600_000 })).toBe(3_600_000);
+    expect(detector.sample({ monotonicMs: 60_000, wallMs: 1_060_000 })).toBe(-3_600_000);
+  });
+
+  it('reports drift at the threshold but not just under it', () => {
+    const detector = new ClockJumpDetector();
+    detector.sample({ monotonicMs: 0, wallMs: 0 });
+    expect(detector.sample({ monotonicMs: 0, wallMs: CLOCK_JUMP_THRESHOLD_MS - 1 })).toBeNull();
+    expect(detector.sample({ monotonicMs: 0, wallMs: 2 * CLOCK_JUMP_THRESHOLD_MS - 1 })).toBe(CLOCK_JUMP_THRESHOLD_MS);
+  });
+
+  it('compares each sample against the one before it', () => {
+    const detector = new ClockJumpDetector();
+    detector.sample({ monotonicMs: 0, wallMs: 0 });
+    detector.sample({ monotonicMs: 30_000, wallMs: 3_630_000 });
+    expect(detector.sample({ monotonicMs: 60_000, wallMs: 3_660_000 })).toBeNull();
+  });
+
+  it('does not read sleep as a jump after a reset', () => {
+    const detector = new ClockJumpDetector();
+    detector.sample({ monotonicMs: 0, wallMs: 0 });
+    // Eight hours asleep: wall time moved, the monotonic clock didn't
+    detector.reset({ monotonicMs: 30_000, wallMs: 8 * 3_600_000 });
+    expect(detector.sample({ monotonicMs: 60_000, wallMs: 8 * 3_600_000 + 30_000 })).toBeNull();
+  });
+
+  it('uses a custom threshold', () => {
+    const detector = new ClockJumpDetector(1_000);
+    detector.sample({ monotonicMs: 0, wallMs: 0 });
+    expect(detector.sample({ monotonicMs: 0, wallMs: 1_000 })).toBe(1_000);
+  });
+});
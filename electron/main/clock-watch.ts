@@ -0,0 +1,75 @@
+// Wall-clock jump detection. If the clock was wrong when a token was signed
+// (bad iat) and the user later corrects it, the token stays invalid until
+// it's re-signed. Comparing how far the wall clock moved against a
+// monotonic clock between samples shows when the clock was changed.
+// The monotonic clock may not advance while the machine sleeps, so the
+// baseline is reset on resume rather than reading sleep time as a jump.
+
+import { powerMonitor } from 'electron';
+import { emitToAll } from './events';
+import { refreshDeveloperToken } from './token';
+
+const CHECK_INTERVAL_MS = 30_000;
+// Timer jitter and NTP slewing stay well under this
+export const CLOCK_JUMP_THRESHOLD_MS = 2 * 60_000;
+
+export interface ClockSample {
+  monotonicMs: number;
+  wallMs: number;
+}
+
+/** Feeds successive samples; returns the drift in ms when the wall clock jumped, otherwise null. */
+export class ClockJumpDetector {
+  private last: ClockSample | null = null;
+
+  constructor(private readonly thresholdMs: number = CLOCK_JUMP_THRESHOLD_MS) {}
+
+  sample(current: ClockSample): number | null {
+    const previous = this.last;
+    this.last = current;
+    if (!previous) return null;
+
+    const drift = current.wallMs - previous.wallMs - (current.monotonicMs - previous.monotonicMs);
+    return Math.abs(drift) >= this.thresholdMs ? drift : null;
+  }
+
+  /** Starts over from the given sample without comparing it to the previous one. */
+  reset(current: ClockSample): void {
+    this.last = current;
+  }
+}
+
+let timer: NodeJS.Timeout | null = null;
+let onResume: (() => void) | null = null;
+
+function now(): ClockSample {
+  return { monotonicMs: performance.now(), wallMs: Date.now() };
+}
+
+export function startClockWatch(): void {
+  if (timer) return;
+
+  const detector = new ClockJumpDetector();
+  detector.sample(now());
+  onResume = () => detector.reset(now());
+  powerMonitor.on('resume', onResume);
+  timer = setInterval(() => {
+    const drift = detector.sample(now());
+    if (drift === null) return;
+
+    console.log(`[TUFFAHI] System clock jumped by ${Math.round(drift / 1000)}s, refreshing developer token`);
+    refreshDeveloperToken()
+      .then((token) => emitToAll('developer-token-refreshed', token))
+      .catch((error) => {
+        console.warn('[TUFFAHI] Token refresh after clock jump failed:', error);
+      });
+  }, CHECK_INTERVAL_MS);
+  timer.unref();
+}
+
+export function stopClockWatch(): void {
+  if (timer) clearInterval(timer);
+  timer = null;
+  if (onResume) powerMonitor.removeListener('resume', onResume);
+  onResume = null;
+}
@@ -0,0 +1,87 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('./store', () => ({
+  getStoreValue: () => undefined,
+  setStoreValue: () => {},
+}));
+
+import { NEAR_END_LEAD_MS, TrackBoundaryWatcher } from './crossfade';
+
+const DURATION = 200_000;
+const CROSSFADE_SECONDS = 6;
+const FADE_START = DURATION - CROSSFADE_SECONDS * 1000;
+const NEAR_END = DURATION - NEAR_END_LEAD_MS;
+
+function channels(events: { channel: string }[]): string[] {
+  return events.map((event) => event.channel);
+}
+
+describe('TrackBoundaryWatcher', () => {
+  it('stays quiet before the boundaries', () => {
+    const watcher = new TrackBoundaryWatcher();
+    expect(watcher.update('a', 0, DURATION, CROSSFADE_SECONDS, 1)).toEqual([]);
+    expect(watcher.update('a', NEAR_END - 1, DURATION, CROSSFADE_SECONDS, 1)).toEqual([]);
+  });
+
+  it('fires track-near-end once with the remaining time', () => {
+    const watcher = new TrackBoundaryWatcher();
+    expect(watcher.update('a', NEAR_END + 500, DURATION, CROSSFADE_SECONDS, 1)).toEqual([
+      { channel: 'track-near-end', payload: { track_id: 'a', remaining_ms: NEAR_END_LEAD_MS - 500 } },
+    ]);
+    expect(watcher.update('a', NEAR_END + 1_000, DURATION, CROSSFADE_SECONDS, 1)).toEqual([]);
+  });
+
+  it('fires crossfade-start once with the next index', () => {
+    const watcher = new TrackBoundaryWatcher();
+    watcher.update('a', NEAR_END, DURATION, CROSSFADE_SECONDS, 1);
+    expect(watcher.update('a', FADE_START, DURATION, CROSSFADE_SECONDS, 3)).toEqual([
+      { channel: 'crossfade-start', payload: { next_index: 3 } },
+    ]);
+    expect(watcher.update('a', FADE_START + 1_000, DURATION, CROSSFADE_SECONDS, 3)).toEqual([]);
+  });
+
+  it('fires both when a single report crosses both boundaries', () => {
+    const watcher = new TrackBoundaryWatcher();
+    expect(channels(watcher.update('a', FADE_START, DURATION, CROSSFADE_SECONDS, null))).toEqual([
+      'track-near-end',
+      'crossfade-start',
+    ]);
+  });
+
+  it('never fires crossfade-start with crossfade off', () => {
+    const watcher = new TrackBoundaryWatcher();
+    expect(channels(watcher.update('a', DURATION - 1, DURATION, 0, 1))).toEqual(['track-near-end']);
+  });
+
+  it('re-arms after seeking back before a boundary', () => {
+    const watcher = new TrackBoundaryWatcher();
+    watcher.update('a', FADE_START, DURATION, CROSSFADE_SECONDS, 1);
+    watcher.update('a', 0, DURATION, CROSSFADE_SECONDS, 1);
+    expect(channels(watcher.update('a', FADE_START, DURATION, CROSSFADE_SECONDS, 1))).toEqual([
+      'track-near-end',
+      'crossfade-start',
+    ]);
+  });
+
+  it('re-arms on a new track', () => {
+    const watcher = new TrackBoundaryWatcher();
+    watcher.update('a', FADE_START, DURATION, CROSSFADE_SECONDS, 1);
+    expect(channels(watcher.update('b', FADE_START, DURATION, CROSSFADE_SECONDS, 2))).toEqual([
+      'track-near-end',
+      'crossfade-start',
+    ]);
+  });
+
+  it('ignores reports without a track or a known duration', () => {
+    const watcher = new TrackBoundaryWatcher();
+    expect(watcher.update(null, 0, DURATION, CROSSFADE_SECONDS, 1)).toEqual([]);
+    expect(watcher.update('a', 5_000, 0, CROSSFADE_SECONDS, 1)).toEqual([]);
+  });
+
+  it('fires near-end from the start of a track shorter than the lead time', () => {
+    const watcher = new TrackBoundaryWatcher();
+    expect(watcher.update('a', 0, 5_000, 0, 1)).toEqual([
+      { channel: 'track-near-end', payload: { track_id: 'a', remaining_ms: 5_000 } },
+    ]);
+  });
+});
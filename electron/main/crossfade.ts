@@ -0,0 +1,126 @@
+// Crossfade timing from track duration metadata.
+// The renderer reports durations for queued tracks; the crossfade window is
+// where the next track should start fading in.
+
+import { getStoreValue, setStoreValue } from './store';
+
+export interface CrossfadeWindow {
+  enabled: boolean;
+  start_ms: number | null;
+}
+
+const DEFAULT_CROSSFADE_SECONDS = 0;
+const MAX_CROSSFADE_SECONDS = 12;
+
+// track id → duration in ms
+const trackDurations = new Map<string, number>();
+
+export function computeCrossfadeWindow(durationMs: number | undefined, crossfadeSeconds: number): CrossfadeWindow {
+  const crossfadeMs = crossfadeSeconds * 1000;
+  // No crossfade configured, unknown duration, or a track too short to fade
+  if (crossfadeMs <= 0 || !durationMs || durationMs <= crossfadeMs) {
+    return { enabled: false, start_ms: null };
+  }
+  return { enabled: true, start_ms: durationMs - crossfadeMs };
+}
+
+export function getCrossfadeSeconds(): number {
+  return getStoreValue<number>('crossfadeSeconds') ?? DEFAULT_CROSSFADE_SECONDS;
+}
+
+export function setCrossfadeSeconds(seconds: number): number {
+  if (!Number.isFinite(seconds) || seconds < 0 || seconds > MAX_CROSSFADE_SECONDS) {
+    throw new Error(`Crossfade must be between 0 and ${MAX_CROSSFADE_SECONDS} seconds`);
+  }
+  setStoreValue('crossfadeSeconds', seconds);
+  return seconds;
+}
+
+export function setTrackDurations(durations: Record<string, number>): void {
+  for (const [id, ms] of Object.entries(durations)) {
+    if (Number.isFinite(ms) && ms > 0) {
+      trackDurations.set(id, Math.floor(ms));
+    }
+  }
+}
+
+export function getTrackDuration(trackId: string): number | undefined {
+  return trackDurations.get(trackId);
+}
+
+export function getCrossfadeWindow(trackId: string): CrossfadeWindow {
+  return computeCrossfadeWindow(trackDurations.get(trackId), getCrossfadeSeconds());
+}
+
+// ── Track boundary events ───────────────────────────────────────────────────
+
+// How long before the end `track-near-end` fires, so the next track can prefetch
+export const NEAR_END_LEAD_MS = 10_000;
+
+export type BoundaryEvent =
+  | { channel: 'crossfade-start'; payload: { next_index: number | null } }
+  | { channel: 'track-near-end'; payload: { track_id: string; remaining_ms: number } };
+
+/**
+ * Turns the stream of reported positions into one-shot boundary events.
+ * Each event fires when playback crosses its boundary going forward, once per
+ * track; seeking back before the boundary (or repeat-one) re-arms it.
+ */
+export class TrackBoundaryWatcher {
+  private trackId: string | null = null;
+  private firedCrossfade = false;
+  private firedNearEnd = false;
+
+  update(
+    trackId: string | null,
+    positionMs: number,
+    durationMs: number,
+    crossfadeSeconds: number,
+    nextIndex: number | null,
+  ): BoundaryEvent[] {
+    if (trackId !== this.trackId) {
+      this.trackId = trackId;
+      this.firedCrossfade = false;
+      this.firedNearEnd = false;
+    }
+    if (!trackId || durationMs <= 0) return [];
+
+    const events: BoundaryEvent[] = [];
+
+    const nearEndAt = Math.max(0, durationMs - NEAR_END_LEAD_MS);
+    if (positionMs < nearEndAt) {
+      this.firedNearEnd = false;
+    } else if (!this.firedNearEnd) {
+      this.firedNearEnd = true;
+      events.push({
+        channel: 'track-near-end',
+        payload: { track_id: trackId, remaining_ms: Math.max(0, durationMs - positionMs) },
+      });
+    }
+
+    const fade = computeCrossfadeWindow(durationMs, crossfadeSeconds);
+    if (fade.start_ms !== null) {
+      if (positionMs < fade.start_ms) {
+        this.firedCrossfade = false;
+      } else if (!this.firedCrossfade) {
+        this.firedCrossfade = true;
+        events.push({ channel: 'crossfade-start', payload: { next_index: nextIndex } });
+      }
+    }
+
+    return events;
+  }
+}
+
+const boundaryWatcher = new TrackBoundaryWatcher();
+
+/** Feeds a reported player position through the watcher; returns events to emit. */
+export function checkTrackBoundaries(
+  trackId: string | null,
+  positionMs: number,
+  durationMs: number,
+  nextIndex: number | null,
+): BoundaryEvent[] {
+  const duration = durationMs > 0 ? durationMs : (trackId ? trackDurations.get(trackId) ?? 0 : 0);
+  return boundaryWatcher.update(trackId, positionMs, duration, getCrossfadeSeconds(), nextIndex);
+}
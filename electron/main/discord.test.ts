@@ -0,0 +1,214 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+
+const { rpc } = vi.hoisted(() => ({
+  rpc: {
+    login: vi.fn(async () => {}),
+    setActivity: vi.fn(async (_activity: unknown) => {}),
+    clearActivity: vi.fn(async () => {}),
+    destroy: vi.fn(async () => {}),
+  },
+}));
+
+vi.mock('electron', () => ({ ipcMain: { handle: vi.fn() } }));
+
+vi.mock('discord-rpc', () => ({
+  Client: class {
+    login = rpc.login;
+    setActivity = rpc.setActivity;
+    clearActivity = rpc.clearActivity;
+    destroy = rpc.destroy;
+  },
+}));
+
+vi.mock('./store', () => ({
+  getStoreValue: () => undefined,
+  setStoreValue: () => {},
+}));
+
+import {
+  buildActivity,
+  discordClearActivity,
+  discordConnect,
+  discordDisconnect,
+  discordSetActivity,
+  getPendingActivity,
+  ActivityParams,
+  PauseGrace,
+} from './discord';
+
+const APP_ID = '12345678901234567';
+
+const track: ActivityParams = {
+  details: 'Song',
+  state: 'by Artist',
+  largeImageKey: 'https://example.com/art.jpg',
+  largeImageText: 'Album',
+};
+
+describe('buildActivity', () => {
+  it('links the track when it has a web URL', () => {
+    const activity = buildActivity({ ...track, trackUrl: 'https://music.apple.com/song/1' });
+    expect(activity.buttons).toEqual([{ label: 'Listen on Apple Music', url: 'https://music.apple.com/song/1' }]);
+  });
+
+  it('falls back to Apple Music for missing or non-web URLs', () => {
+    expect(buildActivity(track).buttons[0].url).toBe('https://music.apple.com');
+    expect(buildActivity({ ...track, trackUrl: 'musics://song/1' }).buttons[0].url).toBe('https://music.apple.com');
+    expect(buildActivity({ ...track, trackUrl: 'not a url' }).buttons[0].url).toBe('https://music.apple.com');
+  });
+
+  it('sets the small image only when both key and text are given', () => {
+    expect(buildActivity({ ...track, smallImageKey: 'play' })).not.toHaveProperty('smallImageKey');
+    expect(buildActivity({ ...track, smallImageKey: 'play', smallImageText: 'Playing' })).toMatchObject({
+      smallImageKey: 'play',
+      smallImageText: 'Playing',
+    });
+  });
+
+  it('includes timestamps while playing', () => {
+    const activity = buildActivity({ ...track, startTimestamp: 1000, endTimestamp: 2000 });
+    expect(activity).toMatchObject({ startTimestamp: 1000, endTimestamp: 2000 });
+  });
+
+  it('omits timestamps while paused', () => {
+    const activity = buildActivity({ ...track, startTimestamp: 1000, endTimestamp: 2000, isPaused: true });
+    expect(activity).not.toHaveProperty('startTimestamp');
+    expect(activity).not.toHaveProperty('endTimestamp');
+  });
+
+  it('ignores an end timestamp without a start', () => {
+    expect(buildActivity({ ...track, endTimestamp: 2000 })).not.toHaveProperty('endTimestamp');
+  });
+});
+
+describe('pending activity', () => {
+  afterEach(async () => {
+    await discordDisconnect();
+    await discordClearActivity();
+    vi.clearAllMocks();
+  });
+
+  it('keeps the latest activity set before connecting and still rejects', async () => {
+    await expect(discordSetActivity({ ...track, details: 'First' })).rejects.toMatchObject({
+      code: 'DISCORD_NOT_CONNECTED',
+    });
+    await expect(discordSetActivity({ ...track, details: 'Second' })).rejects.toThrow();
+    expect(getPendingActivity()?.details).toBe('Second');
+  });
+
+  it('applies the pending activity once on connect', async () => {
+    await discordSetActivity(track).catch(() => {});
+    await discordConnect(APP_ID);
+
+    expect(rpc.setActivity).toHaveBeenCalledTimes(1);
+    expect(rpc.setActivity).toHaveBeenCalledWith(buildActivity(track));
+    expect(getPendingActivity()).toBeNull();
+  });
+
+  it('sends nothing on connect when nothing is pending', async () => {
+    await discordConnect(APP_ID);
+    expect(rpc.setActivity).not.toHaveBeenCalled();
+  });
+
+  it('drops the pending activity when presence is cleared', async () => {
+    await discordSetActivity(track).catch(() => {});
+    await discordClearActivity();
+    await discordConnect(APP_ID);
+
+    expect(getPendingActivity()).toBeNull();
+    expect(rpc.setActivity).not.toHaveBeenCalled();
+  });
+
+  it('stays connected when applying the pending activity fails', async () => {
+    rpc.setActivity.mockRejectedValueOnce(new Error('rate limited'));
+    await discordSetActivity(track).catch(() => {});
+
+    await expect(discordConnect(APP_ID)).resolves.toBeUndefined();
+    expect(getPendingActivity()).toBeNull();
+  });
+});
+
+describe('PauseGrace', () => {
+  const GRACE_MS = 5_000;
+
+  function setup() {
+    vi.useFakeTimers();
+    const onExpire = vi.fn();
+    return { grace: new PauseGrace(onExpire), onExpire };
+  }
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('expires once after staying paused for the grace period', () => {
+    const { grace, onExpire } = setup();
+    grace.update(true, GRACE_MS);
+    expect(grace.isArmed).toBe(true);
+
+    vi.advanceTimersByTime(GRACE_MS);
+    expect(onExpire).toHaveBeenCalledTimes(1);
+    expect(grace.isExpired).toBe(true);
+  });
+
+  it('does not restart the timer on repeated paused updates', () => {
+    const { grace, onExpire } = setup();
+    grace.update(true, GRACE_MS);
+    vi.advanceTimersByTime(GRACE_MS - 1);
+    grace.update(true, GRACE_MS);
+    vi.advanceTimersByTime(1);
+    expect(onExpire).toHaveBeenCalledTimes(1);
+
+    grace.update(true, GRACE_MS);
+    vi.advanceTimersByTime(GRACE_MS);
+    expect(onExpire).toHaveBeenCalledTimes(1);
+  });
+
+  it('cancels the timer on resume', () => {
+    const { grace, onExpire } = setup();
+    grace.update(true, GRACE_MS);
+    grace.update(false, GRACE_MS);
+    vi.advanceTimersByTime(GRACE_MS);
+
+    expect(onExpire).not.toHaveBeenCalled();
+    expect(grace.isArmed).toBe(false);
+  });
+
+  it('clears the expired state on resume', () => {
+    const { grace } = setup();
+    grace.update(true, GRACE_MS);
+    vi.advanceTimersByTime(GRACE_MS);
+    grace.update(false, GRACE_MS);
+
+    expect(grace.isExpired).toBe(false);
+  });
+
+  it('never expires with a zero grace period', () => {
+    const { grace, onExpire } = setup();
+    grace.update(true, 0);
+    vi.advanceTimersByTime(60 * 60_000);
+
+    expect(onExpire).not.toHaveBeenCalled();
+    expect(grace.isArmed).toBe(false);
+  });
+
+  it('expires right away when paused presence is turned off', () => {
+    const { grace, onExpire } = setup();
+    grace.update(true, GRACE_MS, false);
+    expect(onExpire).toHaveBeenCalledTimes(1);
+    expect(grace.isExpired).toBe(true);
+
+    grace.update(true, 0, false);
+    expect(onExpire).toHaveBeenCalledTimes(1);
+  });
+
+  it('expires an armed timer early when paused presence is turned off', () => {
+    const { grace, onExpire } = setup();
+    grace.update(true, GRACE_MS);
+    grace.update(true, GRACE_MS, false);
+    vi.advanceTimersByTime(GRACE_MS);
+
+    expect(onExpire).toHaveBeenCalledTimes(1);
+    expect(grace.isArmed).toBe(false);
+  });
+});
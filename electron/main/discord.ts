@@ -1,23 +1,45 @@
 // Discord Rich Presence Integration
 // Uses discord-rpc npm package
 
+import { AppError } from './errors';
+import { getStoreValue, setStoreValue } from './store';
+
 let client: any = null;
 let isConnected = false;
 
-// Discord Application ID - Create your own at https://discord.com/developers/applications
-const DISCORD_APP_ID = '0000000000000000000';
+// Fallback target for the activity button when the track has no link
+const APPLE_MUSIC_URL = 'https://music.apple.com';
+
+/** Discord application IDs are snowflakes: 17–20 digit decimal numbers. */
+export function validateAppId(appId: string): string {
+  const trimmed = typeof appId === 'string' ? appId.trim() : '';
+  if (!/^\d{17,20}$/.test(trimmed)) {
+    throw new AppError(
+      'INVALID_ARGUMENT',
+      'Invalid Discord application ID — create one at https://discord.com/developers/applications',
+      { app_id: appId },
+    );
+  }
+  return trimmed;
+}
 
-export async function discordConnect(): Promise<void> {
+export function isDiscordConnected(): boolean {
+  return isConnected;
+}
+
+export async function discordConnect(appId: string): Promise<void> {
+  const clientId = validateAppId(appId);
   try {
     // Dynamic import to avoid crash if discord-rpc not available
     const { Client } = await import('discord-rpc');
     client = new Client({ transport: 'ipc' });
-    await client.login({ clientId: DISCORD_APP_ID });
+    await client.login({ clientId });
     isConnected = true;
     console.log('[TUFFAHI] Connected to Discord');
+    await applyPendingActivity();
   } catch (error) {
     isConnected = false;
-    throw new Error(`Failed to connect to Discord: ${error}`);
+    throw new AppError('DISCORD_CONNECT_FAILED', `Failed to connect to Discord: ${error}`);
   }
 }
 
@@ -25,16 +47,17 @@ export async function discordDisconnect(): Promise<void> {
   if (!client || !isConnected) return;
 
   try {
+    pauseGrace.reset();
     await client.destroy();
     isConnected = false;
     client = null;
     console.log('[TUFFAHI] Disconnected from Discord');
   } catch (error) {
-    throw new Error(`Failed to disconnect from Discord: ${error}`);
+    throw new AppError('DISCORD_DISCONNECT_FAILED', `Failed to disconnect from Discord: ${error}`);
   }
 }
 
-export async function discordSetActivity(params: {
+export interface ActivityParams {
   details: string;
   state: string;
   largeImageKey: string;
@@ -43,37 +66,171 @@ export async function discordSetActivity(params: {
   smallImageText?: string;
   startTimestamp?: number;
   endTimestamp?: number;
-}): Promise<void> {
-  if (!client || !isConnected) {
-    throw new Error('Discord not connected');
+  isPaused?: boolean;
+  trackUrl?: string;
+}
+
+function isWebUrl(url: string | undefined): url is string {
+  if (!url) return false;
+  try {
+    return ['https:', 'http:'].includes(new URL(url).protocol);
+  } catch {
+    return false;
   }
+}
 
+// Pure activity builder — no I/O, so every branch is testable without Discord
+export function buildActivity(params: ActivityParams): Record<string, any> {
   const activity: Record<string, any> = {
     details: params.details,
     state: params.state,
     largeImageKey: params.largeImageKey,
     largeImageText: params.largeImageText,
+    buttons: [
+      { label: 'Listen on Apple Music', url: isWebUrl(params.trackUrl) ? params.trackUrl : APPLE_MUSIC_URL },
+    ],
   };
 
-  if (params.smallImageKey) {
+  if (params.smallImageKey && params.smallImageText) {
     activity.smallImageKey = params.smallImageKey;
     activity.smallImageText = params.smallImageText;
   }
 
-  if (params.startTimestamp) {
+  // A paused track has no meaningful progress — omit timestamps so Discord
+  // doesn't keep counting elapsed time
+  if (params.startTimestamp && !params.isPaused) {
     activity.startTimestamp = params.startTimestamp;
     if (params.endTimestamp) {
       activity.endTimestamp = params.endTimestamp;
     }
   }
 
-  await client.setActivity(activity);
+  return activity;
 }
 
-export async function discordClearActivity(): Promise<void> {
+// ── Pause grace period ──────────────────────────────────────────────────────
+
+const DEFAULT_PAUSE_GRACE_SECONDS = 5 * 60;
+
+/**
+ * Clears presence once playback has stayed paused for the grace period.
+ * Resuming cancels the timer; after it fires, paused updates are dropped so
+ * the renderer's periodic refresh doesn't bring the stale status back.
+ * With the idle-presence setting off, a pause clears presence right away.
+ */
+export class PauseGrace {
+  private timer: ReturnType<typeof setTimeout> | null = null;
+  private expired = false;
+
+  constructor(private readonly onExpire: () => void) {}
+
+  get isArmed(): boolean {
+    return this.timer !== null;
+  }
+
+  get isExpired(): boolean {
+    return this.expired;
+  }
+
+  /** graceMs <= 0 disables clearing, unless showWhilePaused is off. */
+  update(isPaused: boolean, graceMs: number, showWhilePaused: boolean = true): void {
+    if (!isPaused) {
+      this.reset();
+      return;
+    }
+    if (!showWhilePaused) {
+      this.expire();
+      return;
+    }
+    if (graceMs <= 0) {
+      this.reset();
+      return;
+    }
+    if (this.timer || this.expired) return;
+    this.timer = setTimeout(() => this.expire(), graceMs);
+  }
+
+  private expire(): void {
+    if (this.timer) clearTimeout(this.timer);
+    this.timer = null;
+    if (this.expired) return;
+    this.expired = true;
+    this.onExpire();
+  }
+
+  reset(): void {
+    if (this.timer) clearTimeout(this.timer);
+    this.timer = null;
+    this.expired = false;
+  }
+}
+
+const pauseGrace = new PauseGrace(() => {
+  if (!client || !isConnected) return;
+  console.log('[TUFFAHI] Paused past grace period, clearing Discord presence');
+  client.clearActivity().catch(() => {});
+});
+
+export function getPauseGraceSeconds(): number {
+  return getStoreValue<number>('discordPauseGraceSeconds') ?? DEFAULT_PAUSE_GRACE_SECONDS;
+}
+
+export function setPauseGraceSeconds(seconds: number): number {
+  if (!Number.isFinite(seconds) || seconds < 0) {
+    throw new AppError('INVALID_ARGUMENT', 'Pause grace period must be 0 (never clear) or more seconds', { seconds });
+  }
+  setStoreValue('discordPauseGraceSeconds', seconds);
+  return seconds;
+}
+
+// Idle presence: whether a paused track is shown at all (then for the grace period)
+export function getShowWhilePaused(): boolean {
+  return getStoreValue<boolean>('discordShowWhilePaused') ?? true;
+}
+
+export function setShowWhilePaused(enabled: boolean): boolean {
+  setStoreValue('discordShowWhilePaused', enabled);
+  return enabled;
+}
+
+// ── Pending activity ────────────────────────────────────────────────────────
+
+// Latest activity requested while disconnected, applied once connected
+let pendingActivity: ActivityParams | null = null;
+
+export function getPendingActivity(): ActivityParams | null {
+  return pendingActivity;
+}
+
+async function applyPendingActivity(): Promise<void> {
+  const params = pendingActivity;
+  pendingActivity = null;
+  if (!params) return;
+  try {
+    await discordSetActivity(params);
+  } catch (error) {
+    console.warn('[TUFFAHI] Failed to apply pending Discord activity:', error);
+  }
+}
+
+export async function discordSetActivity(params: ActivityParams): Promise<void> {
   if (!client || !isConnected) {
-    throw new Error('Discord not connected');
+    // Startup race: keep the latest state and apply it when the connection lands,
+    // but still tell the caller it didn't go through
+    pendingActivity = params;
+    throw new AppError('DISCORD_NOT_CONNECTED', 'Discord not connected');
   }
 
+  pauseGrace.update(!!params.isPaused, getPauseGraceSeconds() * 1000, getShowWhilePaused());
+  if (pauseGrace.isExpired) return;
+
+  await client.setActivity(buildActivity(params));
+}
+
+export async function discordClearActivity(): Promise<void> {
+  pendingActivity = null;
+  if (!client || !isConnected) return;
+
+  pauseGrace.reset();
   await client.clearActivity();
 }
@@ -0,0 +1,81 @@
+import { describe, expect, it, vi } from 'vitest';
+
+// A minimal IPC loop: ipcMain.handle registers, ipcRenderer.invoke calls it
+// and, like Electron, only passes the error message back across
+const { handlers, exposed } = vi.hoisted(() => ({
+  handlers: new Map<string, (event: unknown, ...args: unknown[]) => Promise<unknown>>(),
+  exposed: {} as Record<string, any>,
+}));
+
+vi.mock('electron', () => ({
+  ipcMain: {
+    handle: (channel: string, handler: (event: unknown, ...args: unknown[]) => Promise<unknown>) =>
+      handlers.set(channel, handler),
+  },
+  ipcRenderer: {
+    invoke: async (channel: string, ...args: unknown[]) => {
+      try {
+        return await handlers.get(channel)!({}, ...args);
+      } catch (error) {
+        throw new Error(`Error invoking remote method '${channel}': ${error}`);
+      }
+    },
+  },
+  contextBridge: {
+    exposeInMainWorld: (_key: string, api: Record<string, any>) => Object.assign(exposed, api),
+  },
+}));
+
+import { AppError, handleCommand, toAppError } from './errors';
+await import('../preload/index');
+
+describe('toAppError', () => {
+  it('passes AppErrors through unchanged', () => {
+    const error = new AppError('TOKEN_INVALID', 'expired', { exp: 1 });
+    expect(toAppError(error)).toBe(error);
+  });
+
+  it('wraps Errors and other values as INTERNAL', () => {
+    expect(toAppError(new Error('boom')).toJSON()).toEqual({ code: 'INTERNAL', message: 'boom' });
+    expect(toAppError('plain').toJSON()).toEqual({ code: 'INTERNAL', message: 'plain' });
+  });
+
+  it('omits details from JSON when there are none', () => {
+    expect(JSON.parse(JSON.stringify(new AppError('INTERNAL', 'x')))).toEqual({ code: 'INTERNAL', message: 'x' });
+  });
+});
+
+describe('handleCommand through the preload', () => {
+  it('resolves with the handler result', async () => {
+    handleCommand('get-musickit-init-config', () => ({ app: { name: 'Tuffahi' } }));
+    await expect(exposed.getMusicKitInitConfig()).resolves.toEqual({ app: { name: 'Tuffahi' } });
+  });
+
+  it('passes arguments to the handler', async () => {
+    const handler = vi.fn();
+    handleCommand('set-window-min-size', handler);
+    await exposed.setWindowMinSize('main', 800, 600);
+    expect(handler).toHaveBeenCalledWith({}, 'main', 800, 600);
+  });
+
+  it('rejects with the code, message and details of an AppError', async () => {
+    handleCommand('set-shortcuts', () => {
+      throw new AppError('DUPLICATE_BINDING', 'Ctrl+P is bound twice', { accelerator: 'Ctrl+P' });
+    });
+    await expect(exposed.setShortcuts({})).rejects.toEqual({
+      code: 'DUPLICATE_BINDING',
+      message: 'Ctrl+P is bound twice',
+      details: { accelerator: 'Ctrl+P' },
+    });
+  });
+
+  it('rejects with INTERNAL for unexpected errors, including async ones', async () => {
+    handleCommand('frontend-ready', async () => {
+      throw new TypeError('undefined is not a function');
+    });
+    await expect(exposed.frontendReady()).rejects.toEqual({
+      code: 'INTERNAL',
+      message: 'undefined is not a function',
+    });
+  });
+});
@@ -0,0 +1,71 @@
+// Structured errors for IPC commands: { code, message, details }.
+// ipcMain.handle only carries an error's message to the renderer, so
+// handleCommand() encodes AppErrors into it and the preload decodes them.
+
+import { ipcMain, IpcMainInvokeEvent } from 'electron';
+
+export type ErrorCode =
+  | 'DISCORD_NOT_CONNECTED'
+  | 'DISCORD_CONNECT_FAILED'
+  | 'DISCORD_DISCONNECT_FAILED'
+  | 'TOKEN_NOT_CONFIGURED'
+  | 'TOKEN_INVALID'
+  | 'KEY_INVALID'
+  | 'KEY_VERIFICATION_FAILED'
+  | 'KEY_STORAGE_UNAVAILABLE'
+  | 'WINDOW_NOT_FOUND'
+  | 'INVALID_ARGUMENT'
+  | 'DEBUG_DISABLED'
+  | 'DUPLICATE_BINDING'
+  | 'INTERNAL';
+
+export interface SerializedAppError {
+  code: ErrorCode;
+  message: string;
+  details?: Record<string, unknown>;
+}
+
+// Keep in sync with the preload's decoder
+export const APP_ERROR_PREFIX = 'AppError:';
+
+export class AppError extends Error {
+  constructor(
+    readonly code: ErrorCode,
+    message: string,
+    readonly details?: Record<string, unknown>,
+  ) {
+    super(message);
+    this.name = 'AppError';
+  }
+
+  toJSON(): SerializedAppError {
+    return this.details
+      ? { code: this.code, message: this.message, details: this.details }
+      : { code: this.code, message: this.message };
+  }
+}
+
+/** Anything that isn't already an AppError becomes INTERNAL, keeping its message. */
+export function toAppError(error: unknown): AppError {
+  if (error instanceof AppError) return error;
+  const message = error instanceof Error ? error.message : String(error);
+  return new AppError('INTERNAL', message);
+}
+
+export function windowNotFound(label: string): AppError {
+  return new AppError('WINDOW_NOT_FOUND', `Window not found: ${label}`, { label });
+}
+
+/** ipcMain.handle, but failures reach the renderer as SerializedAppError. */
+export function handleCommand<Args extends unknown[]>(
+  channel: string,
+  handler: (event: IpcMainInvokeEvent, ...args: Args) => unknown,
+): void {
+  ipcMain.handle(channel, async (event, ...args) => {
+    try {
+      return await handler(event, ...(args as Args));
+    } catch (error) {
+      throw new Error(APP_ERROR_PREFIX + JSON.stringify(toAppError(error)));
+    }
+  });
+}
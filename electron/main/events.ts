@@ -0,0 +1,48 @@
+// Broadcast helpers for state events sent to every window.
+// The latest payload per channel is kept so windows opened later can call
+// `replay-events` and sync immediately instead of waiting for the next update.
+
+import { BrowserWindow, WebContents } from 'electron';
+
+export const REPLAYABLE_CHANNELS = ['player-state', 'theme-palette-changed', 'queue-update'] as const;
+export type ReplayableChannel = (typeof REPLAYABLE_CHANNELS)[number];
+
+export class ReplayBuffer {
+  private latest = new Map<string, unknown>();
+
+  record(channel: string, payload: unknown): void {
+    this.latest.set(channel, payload);
+  }
+
+  replay(): Record<string, unknown> {
+    return Object.fromEntries(this.latest);
+  }
+
+  clear(): void {
+    this.latest.clear();
+  }
+}
+
+const buffer = new ReplayBuffer();
+
+export interface EmitOptions {
+  /** Payload to store for replay when it differs from the live one (e.g. a full snapshot instead of a diff). */
+  replayPayload?: unknown;
+  /** Skip the window that originated the change. */
+  exclude?: WebContents;
+}
+
+export function emitToAll(channel: string, payload: unknown, options: EmitOptions = {}): void {
+  if ((REPLAYABLE_CHANNELS as readonly string[]).includes(channel)) {
+    buffer.record(channel, 'replayPayload' in options ? options.replayPayload : payload);
+  }
+
+  for (const window of BrowserWindow.getAllWindows()) {
+    if (window.isDestroyed() || window.webContents === options.exclude) continue;
+    window.webContents.send(channel, payload);
+  }
+}
+
+export function replayEvents(): Record<string, unknown> {
+  return buffer.replay();
+}
@@ -0,0 +1,100 @@
+// Rect math for keeping windows on screen. Pure functions over plain
+// rects so every window-positioning path (restore, mini player) shares
+// the same off-screen handling; callers pass screen.getAllDisplays()
+// work areas as `monitors`.
+
+export interface Rect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface Point {
+  x: number;
+  y: number;
+}
+
+function contains(monitor: Rect, point: Point): boolean {
+  return (
+    point.x >= monitor.x &&
+    point.x < monitor.x + monitor.width &&
+    point.y >= monitor.y &&
+    point.y < monitor.y + monitor.height
+  );
+}
+
+function intersectionArea(a: Rect, b: Rect): number {
+  const width = Math.min(a.x + a.width, b.x + b.width) - Math.max(a.x, b.x);
+  const height = Math.min(a.y + a.height, b.y + b.height) - Math.max(a.y, b.y);
+  return width > 0 && height > 0 ? width * height : 0;
+}
+
+function distanceSquared(monitor: Rect, point: Point): number {
+  const dx = Math.max(monitor.x - point.x, 0, point.x - (monitor.x + monitor.width));
+  const dy = Math.max(monitor.y - point.y, 0, point.y - (monitor.y + monitor.height));
+  return dx * dx + dy * dy;
+}
+
+export function overlapsAnyMonitor(rect: Rect, monitors: Rect[]): boolean {
+  return monitors.some((monitor) => intersectionArea(rect, monitor) > 0);
+}
+
+/** The monitor containing `point`, else the closest one. Null only when there are no monitors. */
+export function nearestMonitor(point: Point, monitors: Rect[]): Rect | null {
+  let best: Rect | null = null;
+  let bestDistance = Infinity;
+  for (const monitor of monitors) {
+    if (contains(monitor, point)) return monitor;
+    const distance = distanceSquared(monitor, point);
+    if (distance < bestDistance) {
+      best = monitor;
+      bestDistance = distance;
+    }
+  }
+  return best;
+}
+
+/**
+ * Moves (and if needed shrinks) `rect` so it's fully visible. A rect whose
+ * corners are all on some monitor is left alone, so windows spanning
+ * several monitors stay put. Otherwise it lands on the monitor it overlaps
+ * most, or the one nearest its center when it's entirely off-screen.
+ */
+export function clampToMonitors(rect: Rect, monitors: Rect[]): Rect {
+  if (monitors.length === 0) return rect;
+
+  const right = rect.x + rect.width - 1;
+  const bottom = rect.y + rect.height - 1;
+  const corners: Point[] = [
+    { x: rect.x, y: rect.y },
+    { x: right, y: rect.y },
+    { x: rect.x, y: bottom },
+    { x: right, y: bottom },
+  ];
+  if (corners.every((corner) => monitors.some((monitor) => contains(monitor, corner)))) {
+    return rect;
+  }
+
+  let target: Rect | null = null;
+  let bestOverlap = 0;
+  for (const monitor of monitors) {
+    const overlap = intersectionArea(rect, monitor);
+    if (overlap > bestOverlap) {
+      target = monitor;
+      bestOverlap = overlap;
+    }
+  }
+  if (!target) {
+    target = nearestMonitor({ x: rect.x + rect.width / 2, y: rect.y + rect.height / 2 }, monitors)!;
+  }
+
+  const width = Math.min(rect.width, target.width);
+  const height = Math.min(rect.height, target.height);
+  return {
+    x: Math.min(Math.max(rect.x, target.x), target.x + target.width - width),
+    y: Math.min(Math.max(rect.y, target.y), target.y + target.height - height),
+    width,
+    height,
+  };
+}
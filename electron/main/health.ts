@@ -0,0 +1,71 @@
+// Local health-check endpoint for supervised (kiosk) deployments.
+// Bound to localhost only — process supervisors poll GET /healthz.
+
+import { createServer, Server } from 'http';
+import { isDeveloperTokenValid } from './token';
+import { isDiscordConnected } from './discord';
+
+let server: Server | null = null;
+let serverPort: number | null = null;
+
+export interface HealthReport {
+  status: 'ok';
+  uptime_s: number;
+  token_valid: boolean;
+  discord_connected: boolean;
+}
+
+export async function getHealthReport(): Promise<HealthReport> {
+  return {
+    status: 'ok',
+    uptime_s: Math.floor(process.uptime()),
+    token_valid: await isDeveloperTokenValid(),
+    discord_connected: isDiscordConnected(),
+  };
+}
+
+export async function startHealthServer(port: number): Promise<number> {
+  if (!Number.isInteger(port) || port < 1 || port > 65535) {
+    throw new Error(`Invalid health server port: ${port}`);
+  }
+  if (server) {
+    if (serverPort === port) return port;
+    await stopHealthServer();
+  }
+
+  const instance = createServer(async (req, res) => {
+    if (req.method === 'GET' && req.url?.split('?')[0] === '/healthz') {
+      const report = await getHealthReport();
+      res.writeHead(200, { 'Content-Type': 'application/json' });
+      res.end(JSON.stringify(report));
+      return;
+    }
+    res.writeHead(404, { 'Content-Type': 'application/json' });
+    res.end(JSON.stringify({ error: 'not found' }));
+  });
+
+  await new Promise<void>((resolve, reject) => {
+    instance.once('error', reject);
+    instance.listen(port, '127.0.0.1', () => {
+      instance.off('error', reject);
+      resolve();
+    });
+  }).catch((error) => {
+    throw new Error(`Failed to start health server on port ${port}: ${error}`);
+  });
+
+  server = instance;
+  serverPort = port;
+  console.log(`[TUFFAHI] Health server listening on http://127.0.0.1:${port}/healthz`);
+  return port;
+}
+
+export async function stopHealthServer(): Promise<void> {
+  if (!server) return;
+
+  const instance = server;
+  server = null;
+  serverPort = null;
+  await new Promise<void>((resolve) => instance.close(() => resolve()));
+  console.log('[TUFFAHI] Health server stopped');
+}
@@ -0,0 +1,91 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({ ipcMain: { handle: vi.fn() } }));
+
+import {
+  CsvRecordReader,
+  HistoryEntry,
+  indexColumns,
+  keepLatestPlay,
+  parsePlayActivityRow,
+} from './history-import';
+
+const columns = indexColumns(['\uFEFFEvent Start Timestamp', 'Song Name', 'Artist Name', 'Play Duration Milliseconds']);
+
+function entry(track: string, artist: string, playedAt: number): HistoryEntry {
+  return { played_at: playedAt, track, artist, ms_played: 1, source: 'apple-export' };
+}
+
+describe('indexColumns', () => {
+  it('strips a BOM and falls back to alternate column names', () => {
+    expect(indexColumns(['\uFEFFEvent Received Timestamp', 'Content Name'])).toEqual({
+      timestamp: 0,
+      track: 1,
+      artist: -1,
+      msPlayed: -1,
+    });
+  });
+
+  it('rejects files without a timestamp or song column', () => {
+    expect(() => indexColumns(['Song Name', 'Artist Name'])).toThrowError(
+      expect.objectContaining({ code: 'INVALID_ARGUMENT' }),
+    );
+  });
+});
+
+describe('parsePlayActivityRow', () => {
+  it('maps a play', () => {
+    expect(parsePlayActivityRow(['2024-03-01T10:00:00Z', ' Song ', 'Artist', '180000'], columns)).toEqual({
+      played_at: Date.parse('2024-03-01T10:00:00Z'),
+      track: 'Song',
+      artist: 'Artist',
+      ms_played: 180_000,
+      source: 'apple-export',
+    });
+  });
+
+  it('skips zero-length and unparseable durations', () => {
+    expect(parsePlayActivityRow(['2024-03-01T10:00:00Z', 'Song', 'Artist', '0'], columns)).toBeNull();
+    expect(parsePlayActivityRow(['2024-03-01T10:00:00Z', 'Song', 'Artist', ''], columns)).toBeNull();
+  });
+
+  it('skips rows without a song or a valid timestamp', () => {
+    expect(parsePlayActivityRow(['2024-03-01T10:00:00Z', '', 'Artist', '1000'], columns)).toBeNull();
+    expect(parsePlayActivityRow(['not a date', 'Song', 'Artist', '1000'], columns)).toBeNull();
+    expect(parsePlayActivityRow([], columns)).toBeNull();
+  });
+
+  it('keeps plays from exports without a duration column', () => {
+    const noDuration = indexColumns(['Event Start Timestamp', 'Song Name']);
+    expect(parsePlayActivityRow(['2024-03-01T10:00:00Z', 'Song'], noDuration)).toMatchObject({
+      artist: '',
+      ms_played: 0,
+    });
+  });
+});
+
+describe('CsvRecordReader', () => {
+  it('splits plain and quoted fields', () => {
+    expect(new CsvRecordReader().push('a,"b, c","say ""hi"""')).toEqual(['a', 'b, c', 'say "hi"']);
+  });
+
+  it('joins a quoted field that spans lines', () => {
+    const reader = new CsvRecordReader();
+    expect(reader.push('a,"first')).toBeNull();
+    expect(reader.push('second",b')).toEqual(['a', 'first\nsecond', 'b']);
+    expect(reader.push('c,d')).toEqual(['c', 'd']);
+  });
+});
+
+describe('keepLatestPlay', () => {
+  it('keeps the most recent play per song, ignoring case', () => {
+    const latest = new Map<string, HistoryEntry>();
+    keepLatestPlay(latest, entry('Song', 'Artist', 2));
+    keepLatestPlay(latest, entry('song', 'ARTIST', 3));
+    keepLatestPlay(latest, entry('Song', 'Artist', 1));
+    keepLatestPlay(latest, entry('Other', 'Artist', 1));
+
+    expect(latest.size).toBe(2);
+    expect([...latest.values()][0]).toMatchObject({ track: 'song', played_at: 3 });
+  });
+});
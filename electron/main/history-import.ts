@@ -0,0 +1,172 @@
+// Seeds local listening history from Apple's privacy data export
+// ("Apple Music Play Activity.csv"). The export can be hundreds of MB, so
+// it's streamed line by line. Play history lives in the renderer's storage,
+// so this only parses: the renderer merges the result into its history.
+
+import { createReadStream, existsSync } from 'fs';
+import { createInterface } from 'readline';
+import { AppError } from './errors';
+
+export interface HistoryEntry {
+  played_at: number;
+  track: string;
+  artist: string;
+  ms_played: number;
+  source: 'apple-export';
+}
+
+export interface ImportResult {
+  // One entry per song, at its most recent play, newest first
+  entries: HistoryEntry[];
+  // Rows that weren't real plays
+  skipped: number;
+}
+
+// Column names vary between export versions; first match wins
+const COLUMNS = {
+  timestamp: ['Event Start Timestamp', 'Event Received Timestamp', 'Event End Timestamp'],
+  track: ['Song Name', 'Content Name'],
+  artist: ['Artist Name', 'Container Artist Name'],
+  msPlayed: ['Play Duration Milliseconds'],
+};
+
+/**
+ * Incremental RFC 4180 reader: feed it physical lines, get back a record
+ * once any quoted field spanning several lines is closed.
+ */
+export class CsvRecordReader {
+  private fields: string[] = [];
+  private field = '';
+  private inQuotes = false;
+
+  push(line: string): string[] | null {
+    for (let i = 0; i < line.length; i++) {
+      const ch = line[i];
+      if (this.inQuotes) {
+        if (ch === '"' && line[i + 1] === '"') {
+          this.field += '"';
+          i++;
+        } else if (ch === '"') {
+          this.inQuotes = false;
+        } else {
+          this.field += ch;
+        }
+      } else if (ch === '"') {
+        this.inQuotes = true;
+      } else if (ch === ',') {
+        this.fields.push(this.field);
+        this.field = '';
+      } else {
+        this.field += ch;
+      }
+    }
+
+    if (this.inQuotes) {
+      // Newline inside a quoted field
+      this.field += '\n';
+      return null;
+    }
+
+    const record = [...this.fields, this.field];
+    this.fields = [];
+    this.field = '';
+    return record;
+  }
+}
+
+export type ColumnIndex = Record<keyof typeof COLUMNS, number>;
+
+export function indexColumns(header: string[]): ColumnIndex {
+  const names = header.map((name) => name.trim().replace(/^\uFEFF/, ''));
+  const find = (candidates: string[]): number => {
+    for (const candidate of candidates) {
+      const index = names.indexOf(candidate);
+      if (index !== -1) return index;
+    }
+    return -1;
+  };
+
+  const index = {
+    timestamp: find(COLUMNS.timestamp),
+    track: find(COLUMNS.track),
+    artist: find(COLUMNS.artist),
+    msPlayed: find(COLUMNS.msPlayed),
+  };
+  if (index.timestamp === -1 || index.track === -1) {
+    throw new AppError(
+      'INVALID_ARGUMENT',
+      'Not an Apple Music Play Activity export: missing timestamp or song name columns',
+    );
+  }
+  return index;
+}
+
+/** Maps one CSV row to a history entry; null for rows that aren't real plays. */
+export function parsePlayActivityRow(row: string[], columns: ColumnIndex): HistoryEntry | null {
+  const cell = (index: number): string => (index >= 0 ? (row[index] ?? '').trim() : '');
+
+  const track = cell(columns.track);
+  const playedAt = Date.parse(cell(columns.timestamp));
+  const msPlayed = Number.parseInt(cell(columns.msPlayed), 10);
+  if (!track || Number.isNaN(playedAt)) return null;
+  // Zero-length events are skips/previews, not listens
+  if (columns.msPlayed !== -1 && !(msPlayed > 0)) return null;
+
+  return {
+    played_at: playedAt,
+    track,
+    artist: cell(columns.artist),
+    ms_played: Number.isNaN(msPlayed) ? 0 : msPlayed,
+    source: 'apple-export',
+  };
+}
+
+export function songKey(entry: Pick<HistoryEntry, 'track' | 'artist'>): string {
+  return `${entry.track.toLowerCase()}|${entry.artist.toLowerCase()}`;
+}
+
+/**
+ * Keeps only each song's most recent play in `latest` (history holds one
+ * entry per song).
+ */
+export function keepLatestPlay(latest: Map<string, HistoryEntry>, entry: HistoryEntry): void {
+  const key = songKey(entry);
+  const previous = latest.get(key);
+  if (!previous || entry.played_at > previous.played_at) latest.set(key, entry);
+}
+
+export async function importAppleMusicExport(csvPath: string): Promise<ImportResult> {
+  if (!existsSync(csvPath)) {
+    throw new AppError('INVALID_ARGUMENT', `Export file not found: ${csvPath}`, { path: csvPath });
+  }
+
+  const reader = new CsvRecordReader();
+  const lines = createInterface({ input: createReadStream(csvPath, 'utf-8'), crlfDelay: Infinity });
+
+  let columns: ColumnIndex | null = null;
+  const latest = new Map<string, HistoryEntry>();
+  let plays = 0;
+  let skipped = 0;
+
+  for await (const line of lines) {
+    const row = reader.push(line);
+    if (!row) continue;
+
+    if (!columns) {
+      columns = indexColumns(row);
+      continue;
+    }
+
+    const entry = parsePlayActivityRow(row, columns);
+    if (!entry) {
+      skipped++;
+      continue;
+    }
+    plays++;
+    keepLatestPlay(latest, entry);
+  }
+
+  const entries = [...latest.values()].sort((a, b) => b.played_at - a.played_at);
+  console.log(`[TUFFAHI] Parsed ${plays} plays of ${entries.length} songs from Apple Music export (${skipped} skipped)`);
+  return { entries, skipped };
+}
@@ -0,0 +1,59 @@
+// Localized strings for native UI (tray, menus).
+// Locale files are bundled; the system locale is used unless the user picks
+// a language explicitly. Missing keys fall back to English.
+
+import { app } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import en from './locales/en.json';
+import de from './locales/de.json';
+import fr from './locales/fr.json';
+import es from './locales/es.json';
+import ar from './locales/ar.json';
+
+export type StringKey = keyof typeof en;
+type Catalog = Partial<Record<StringKey, string>>;
+
+export const CATALOGS: Record<string, Catalog> = { en, de, fr, es, ar };
+
+const listeners = new Set<() => void>();
+
+/** Picks the best bundled catalog for a BCP 47 code: exact, then base language, then English. */
+export function resolveLanguage(code: string | null | undefined): string {
+  if (!code) return 'en';
+  const normalized = code.toLowerCase().replace('_', '-');
+  if (CATALOGS[normalized]) return normalized;
+  const base = normalized.split('-')[0];
+  return CATALOGS[base] ? base : 'en';
+}
+
+export function translate(key: StringKey, language: string): string {
+  return CATALOGS[resolveLanguage(language)][key] ?? en[key];
+}
+
+export function getLanguage(): string {
+  return resolveLanguage(getStoreValue<string>('language') ?? app.getLocale());
+}
+
+export function setLanguage(code: string | null): string {
+  if (code === null) {
+    setStoreValue('language', null);
+  } else {
+    if (!/^[a-z]{2,3}([-_][a-z0-9]{2,8})*$/i.test(code)) {
+      throw new Error(`Invalid language code: ${code}`);
+    }
+    setStoreValue('language', code);
+  }
+
+  const resolved = getLanguage();
+  listeners.forEach((listener) => listener());
+  return resolved;
+}
+
+export function t(key: StringKey): string {
+  return translate(key, getLanguage());
+}
+
+export function onLanguageChanged(listener: () => void): () => void {
+  listeners.add(listener);
+  return () => listeners.delete(listener);
+}
@@ -5,12 +5,43 @@ import {
   Menu,
   nativeImage,
   globalShortcut,
+  screen,
   shell,
 } from 'electron';
 import { join } from 'path';
 import { existsSync } from 'fs';
 import { registerIpcHandlers, getMiniPlayerOnClose, getIsMiniPlayerMode } from './ipc-handlers';
 import { openAuthWindow } from './auth-window';
+import { stopHealthServer } from './health';
+import { stopRemoteControl } from './remote';
+import { getBranding } from './branding';
+import { getPlaybackModes } from './playback-modes';
+import { repairArtworkCache } from './artwork-cache';
+import { enforceCacheBudget } from './cache-budget';
+import { getStoreValue, setStoreValue } from './store';
+import { needsSetup, openSetupWindow } from './setup';
+import { flushPlaybackSnapshot } from './player-state';
+import { onLanguageChanged, t } from './i18n';
+import { flushTelemetry, initTelemetry } from './telemetry';
+import { initAudioInterruptions } from './audio-session';
+import { prewarmConnections } from './api-config';
+import { startClockWatch } from './clock-watch';
+import { captureBackendLogs, closeLog } from './log';
+import { getMediaKeysEnabled, registerMediaKeys } from './media-keys';
+import { getWindowByLabel, getWindowMinSize, registerWindow } from './windows';
+import { clampToMonitors, Rect } from './geometry';
+import { saveMiniPlayerPosition } from './mini-player';
+import { setMainWindowGate, ShowOnReady } from './window-ready';
+import {
+  buildOutputDeviceSubmenu,
+  clearOutputDevices,
+  getOutputDevices,
+  onOutputDevicesChanged,
+  setCurrentOutputDevice,
+} from './output-devices';
+
+// Backend console output goes to the log file alongside forwarded frontend logs
+captureBackendLogs();
 
 // ─── Wayland support ──────────────────────────────────────────────────────────
 // Enable native Wayland when available, fall back to X11 otherwise.
@@ -96,18 +127,25 @@ function createSplashWindow(): void {
 }
 
 // ─── Main Window ──────────────────────────────────────────────────────────────
+function savedMainWindowBounds(): Rect | null {
+  const saved = getStoreValue<Rect>('mainWindowBounds');
+  if (!saved) return null;
+  return clampToMonitors(saved, screen.getAllDisplays().map((display) => display.workArea));
+}
+
 function createMainWindow(): void {
   const preloadPath = join(__dirname, '../preload/index.mjs');
+  const minSize = getWindowMinSize('main')!;
+  const bounds = savedMainWindowBounds();
 
   mainWindow = new BrowserWindow({
-    width: 1280,
-    height: 800,
-    minWidth: 800,
-    minHeight: 600,
+    ...(bounds ?? { width: 1280, height: 800 }),
+    minWidth: minSize.width,
+    minHeight: minSize.height,
     resizable: true,
-    center: true,
+    center: !bounds,
     show: false,
-    title: 'Tuffahi',
+    title: getBranding().appName,
     backgroundColor: '#0a0a0a',
     webPreferences: {
       preload: preloadPath,
@@ -121,6 +159,8 @@ function createMainWindow(): void {
     },
   });
 
+  registerWindow('main', mainWindow);
+
   // Handle window.open() calls from the renderer
   mainWindow.webContents.setWindowOpenHandler(({ url }) => {
     // Apple auth URLs → open our custom auth window
@@ -128,7 +168,9 @@ function createMainWindow(): void {
       url.includes('authorize.music.apple.com') ||
       url.includes('buy.itunes.apple.com')
     ) {
-      openAuthWindow(mainWindow!, url);
+      openAuthWindow(mainWindow!, url).catch((error) => {
+        console.warn('[TUFFAHI] Failed to open auth window:', error);
+      });
       return { action: 'deny' };
     }
     // Everything else → system browser
@@ -143,6 +185,11 @@ function createMainWindow(): void {
     mainWindow.loadFile(join(__dirname, '../renderer/index.html'));
   }
 
+  // Restore persisted shuffle/repeat once the renderer is listening
+  mainWindow.webContents.on('did-finish-load', () => {
+    mainWindow?.webContents.send('playback-modes-restored', getPlaybackModes());
+  });
+
   // Open DevTools with F12 or Ctrl+Shift+I
   mainWindow.webContents.on('before-input-event', (_event, input) => {
     if (
@@ -153,18 +200,30 @@ function createMainWindow(): void {
     }
   });
 
-  // Splash → main window transition
-  mainWindow.once('ready-to-show', () => {
-    mainWindow?.show();
+  // Splash → main window transition, once the renderer reports ready
+  setMainWindowGate(
+    new ShowOnReady((reason) => {
+      if (reason === 'timeout') {
+        console.log('[TUFFAHI] Frontend not ready in time, showing main window anyway');
+      }
+      mainWindow?.show();
+      mainWindow?.focus();
 
-    if (splashWindow && !splashWindow.isDestroyed()) {
-      splashWindow.close();
-      splashWindow = null;
-    }
-  });
+      if (splashWindow && !splashWindow.isDestroyed()) {
+        splashWindow.close();
+        splashWindow = null;
+      }
+    }),
+  );
 
   // Hide instead of close so MusicKit keeps playing
   mainWindow.on('close', (e) => {
+    // Mini player bounds aren't the ones to come back to
+    if (mainWindow && !getIsMiniPlayerMode()) {
+      setStoreValue('mainWindowBounds', mainWindow.getBounds());
+    } else if (mainWindow) {
+      saveMiniPlayerPosition(mainWindow);
+    }
     if (!(app as any).isQuitting) {
       e.preventDefault();
       if (getIsMiniPlayerMode()) {
@@ -183,58 +242,95 @@ function createMainWindow(): void {
 
   mainWindow.on('closed', () => {
     mainWindow = null;
+    setMainWindowGate(null);
+    clearOutputDevices();
   });
 }
 
-// ─── System Tray ──────────────────────────────────────────────────────────────
-function setupTray(): void {
-  let icon: Electron.NativeImage;
-  try {
-    const iconPath = join(__dirname, '../../resources/icons/32x32.png');
-    icon = nativeImage.createFromPath(iconPath);
-    if (!icon.isEmpty()) {
-      icon = icon.resize({ width: 16, height: 16 });
+/** Shows the main window, recreating it (at its saved bounds) if it was destroyed. */
+function showMainWindow(): void {
+  if (!mainWindow || mainWindow.isDestroyed()) {
+    // The main window is created when the setup wizard finishes
+    const setupWindow = getWindowByLabel('setup');
+    if (setupWindow) {
+      setupWindow.focus();
+      return;
     }
-  } catch {
-    icon = nativeImage.createEmpty();
+    console.log('[TUFFAHI] Main window is gone, recreating it');
+    // Shown by the ready gate once the frontend loads
+    createMainWindow();
+    return;
   }
+  if (mainWindow.isMinimized()) mainWindow.restore();
+  mainWindow.show();
+  mainWindow.focus();
+}
 
-  tray = new Tray(icon);
-  tray.setToolTip('Tuffahi');
+// ─── System Tray ──────────────────────────────────────────────────────────────
+function selectOutputDevice(id: string): void {
+  setCurrentOutputDevice(id);
+  mainWindow?.webContents.send('set-output-device', id);
+}
+
+function rebuildTrayMenu(): void {
+  if (!tray) return;
+
+  const { devices, currentDeviceId } = getOutputDevices();
 
   const contextMenu = Menu.buildFromTemplate([
     {
-      label: 'Play/Pause',
+      label: t('tray.playPause'),
       click: () => mainWindow?.webContents.send('tray-play-pause'),
     },
     {
-      label: 'Next',
+      label: t('tray.next'),
       click: () => mainWindow?.webContents.send('tray-next'),
     },
     {
-      label: 'Previous',
+      label: t('tray.previous'),
       click: () => mainWindow?.webContents.send('tray-previous'),
     },
     { type: 'separator' },
     {
-      label: 'Show Window',
-      click: () => {
-        mainWindow?.show();
-        mainWindow?.focus();
-      },
+      label: t('tray.outputDevice'),
+      submenu: buildOutputDeviceSubmenu(devices, currentDeviceId, selectOutputDevice, t('tray.noOutputDevices')),
+    },
+    { type: 'separator' },
+    {
+      label: t('tray.showWindow'),
+      click: () => showMainWindow(),
     },
     {
-      label: 'Quit',
+      label: t('tray.quit'),
       click: () => app.quit(),
     },
   ]);
 
   tray.setContextMenu(contextMenu);
+}
 
-  tray.on('click', () => {
-    mainWindow?.show();
-    mainWindow?.focus();
-  });
+function setupTray(): void {
+  const branding = getBranding();
+
+  let icon: Electron.NativeImage;
+  try {
+    const iconPath = branding.iconPath ?? join(__dirname, '../../resources/icons/32x32.png');
+    icon = nativeImage.createFromPath(iconPath);
+    if (!icon.isEmpty()) {
+      icon = icon.resize({ width: 16, height: 16 });
+    }
+  } catch {
+    icon = nativeImage.createEmpty();
+  }
+
+  tray = new Tray(icon);
+  tray.setToolTip(branding.trayTooltip);
+
+  rebuildTrayMenu();
+  onOutputDevicesChanged(rebuildTrayMenu);
+  onLanguageChanged(rebuildTrayMenu);
+
+  tray.on('click', () => showMainWindow());
 }
 
 // ─── App Lifecycle ────────────────────────────────────────────────────────────
@@ -243,27 +339,49 @@ app.whenReady().then(() => {
   Menu.setApplicationMenu(null);
 
   registerIpcHandlers(getMainWindow);
+  initTelemetry();
+  initAudioInterruptions();
+  startClockWatch();
+  if (getStoreValue<boolean>('prewarmConnections') !== false) {
+    prewarmConnections();
+  }
 
-  createSplashWindow();
-  createMainWindow();
-  setupTray();
+  // Rescan the artwork cache if it may have been modified externally
+  if (getStoreValue<boolean>('repairArtworkCacheOnStartup')) {
+    try {
+      repairArtworkCache();
+    } catch (error) {
+      console.warn('[TUFFAHI] Artwork cache repair failed:', error);
+    }
+  }
+
+  try {
+    enforceCacheBudget();
+  } catch (error) {
+    console.warn('[TUFFAHI] Cache budget enforcement failed:', error);
+  }
 
-  app.on('activate', () => {
-    if (!mainWindow) {
+  if (needsSetup()) {
+    openSetupWindow(() => {
+      createSplashWindow();
       createMainWindow();
-    } else {
-      mainWindow.show();
-    }
-  });
+    });
+  } else {
+    createSplashWindow();
+    createMainWindow();
+  }
+  setupTray();
+  if (getMediaKeysEnabled()) {
+    registerMediaKeys();
+  }
+
+  app.on('activate', () => showMainWindow());
 });
 
 // Second instance → focus existing window
 app.on('second-instance', () => {
-  if (mainWindow) {
-    if (mainWindow.isMinimized()) mainWindow.restore();
-    mainWindow.show();
-    mainWindow.focus();
-  }
+  if (!app.isReady()) return;
+  showMainWindow();
 });
 
 // Keep tray alive when all windows close
@@ -273,9 +391,14 @@ app.on('window-all-closed', () => {
 
 app.on('before-quit', () => {
   (app as any).isQuitting = true;
+  flushPlaybackSnapshot();
+  flushTelemetry().catch(() => {});
 });
 
 app.on('will-quit', () => {
   globalShortcut.unregisterAll();
+  stopHealthServer().catch(() => {});
+  stopRemoteControl().catch(() => {});
   tray?.destroy();
+  closeLog();
 });
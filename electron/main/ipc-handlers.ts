@@ -1,12 +1,99 @@
-import { ipcMain, globalShortcut, BrowserWindow, app } from 'electron';
-import { getDeveloperToken, refreshDeveloperToken, isMusicKitConfigured } from './token';
+import { ipcMain, globalShortcut, BrowserWindow, app, screen } from 'electron';
+import {
+  getDeveloperToken,
+  refreshDeveloperToken,
+  isMusicKitConfigured,
+  getSigningKeyFingerprint,
+  rotatePrivateKey,
+  getAuthDebugEnabled,
+  setAuthDebugEnabled,
+  getAuthDebugTrace,
+  getDeveloperTokenExpiry,
+  setMusicKitConfig,
+} from './token';
 import { openAuthWindow } from './auth-window';
-import { discordConnect, discordDisconnect, discordSetActivity, discordClearActivity } from './discord';
+import { clampToMonitors } from './geometry';
+import {
+  discordConnect,
+  discordDisconnect,
+  discordSetActivity,
+  discordClearActivity,
+  getPauseGraceSeconds,
+  getShowWhilePaused,
+  setPauseGraceSeconds,
+  setShowWhilePaused,
+} from './discord';
+import { startHealthServer, stopHealthServer } from './health';
+import { startRemoteControl, stopRemoteControl } from './remote';
+import { getBranding } from './branding';
+import { checkPlaybackPrerequisites } from './playback-check';
+import { generateListeningRecap, RecapTrack } from './recap';
+import { frontendLog } from './log';
+import { getAutoplay, reportQueueEnded, setAutoplay } from './autoplay';
+import { getScrobbleFullOnly, scrobbleEligibility, setScrobbleFullOnly } from './scrobble';
+import { clearShortcuts, getEffectiveShortcuts, setShortcuts } from './shortcuts';
+import { getMediaKeysEnabled, registerMediaKeys, setMediaKeysEnabled } from './media-keys';
+import { getMusicKitInitConfig, setContentRatingFilter } from './musickit-config';
+import { getApiBaseUrl, setApiBaseUrl, testDeveloperToken } from './api-config';
+import { getOutputDevices, reportOutputDevices, OutputDevice } from './output-devices';
+import { getPlaybackModes, setPlaybackModes, RepeatMode } from './playback-modes';
+import { cacheLibrarySnapshot, getLibrarySnapshot, LibrarySnapshotItem } from './library-snapshot';
+import { importAppleMusicExport } from './history-import';
+import {
+  getArtworkQuality,
+  getCachedArtwork,
+  getEffectiveArtworkQuality,
+  repairArtworkCache,
+  resolveArtworkUrl,
+  setArtworkQuality,
+  setFallbackArtwork,
+  setNetworkMetered,
+} from './artwork-cache';
+import { getCacheUsage, setCacheBudget } from './cache-budget';
+import { getActivePalette, prefetchPalettes, setActiveArtwork } from './palette';
+import { applyQueueUpdate, getQueue, QueueUpdate } from './queue';
+import { completeSetup } from './setup';
+import {
+  getPlaybackSnapshot,
+  getPlayerState,
+  PlayerStateReport,
+  requestSeek,
+  setPlayerState,
+  setPositionSaveInterval,
+} from './player-state';
+import { getLanguage, setLanguage } from './i18n';
+import { getProcessStats, setPerfOverlay } from './process-stats';
+import { getTelemetryStatus, recordTelemetryEvent, setTelemetry } from './telemetry';
+import { getWindowMinSize, setTrafficLightInset, setWindowMinSize } from './windows';
+import { handleCommand, windowNotFound } from './errors';
+import { frontendReady } from './window-ready';
+import { emitToAll, replayEvents } from './events';
+import {
+  checkTrackBoundaries,
+  getCrossfadeSeconds,
+  getCrossfadeWindow,
+  setCrossfadeSeconds,
+  setTrackDurations,
+} from './crossfade';
+import {
+  applyFullscreenBehavior,
+  dispatchArtAction,
+  getMiniPlayerArtAction,
+  getMiniPlayerPosition,
+  getShowOverFullscreen,
+  MINI_PLAYER_SIZE,
+  resetFullscreenBehavior,
+  resolveMiniPlayerBounds,
+  saveMiniPlayerPosition,
+  setMiniPlayerArtAction,
+  setMiniPlayerFullscreenBehavior,
+} from './mini-player';
 
 // Close behavior flag: when true, closing main window opens mini player
 let miniPlayerOnClose = false;
 let isMiniPlayerMode = false;
 let savedBounds: Electron.Rectangle | null = null;
+let miniPlayerMovedListener: (() => void) | null = null;
 
 export function getMiniPlayerOnClose(): boolean {
   return miniPlayerOnClose;
@@ -16,35 +103,105 @@ export function getIsMiniPlayerMode(): boolean {
   return isMiniPlayerMode;
 }
 
+function exitMiniPlayer(mainWindow: BrowserWindow): void {
+  if (!isMiniPlayerMode) return;
+
+  isMiniPlayerMode = false;
+
+  saveMiniPlayerPosition(mainWindow);
+  if (miniPlayerMovedListener) {
+    mainWindow.off('moved', miniPlayerMovedListener);
+    miniPlayerMovedListener = null;
+  }
+
+  // Restore window properties
+  resetFullscreenBehavior(mainWindow);
+  mainWindow.setAlwaysOnTop(false);
+  mainWindow.setSkipTaskbar(false);
+  mainWindow.setResizable(true);
+  const minSize = getWindowMinSize('main')!;
+  mainWindow.setMinimumSize(minSize.width, minSize.height);
+
+  // Restore saved bounds, pulled back on screen if that display is gone
+  if (savedBounds) {
+    mainWindow.setBounds(clampToMonitors(savedBounds, screen.getAllDisplays().map((display) => display.workArea)));
+    savedBounds = null;
+  } else {
+    mainWindow.setSize(1280, 800);
+    mainWindow.center();
+  }
+
+  // Tell renderer to switch back to main view
+  mainWindow.webContents.send('exit-mini-player');
+  mainWindow.show();
+  mainWindow.focus();
+}
+
 export function registerIpcHandlers(getMainWindow: () => BrowserWindow | null): void {
+  const requireMainWindow = (): BrowserWindow => {
+    const mainWindow = getMainWindow();
+    if (!mainWindow) throw windowNotFound('main');
+    return mainWindow;
+  };
+
   // ── Token ─────────────────────────────────────────────────────────────────
-  ipcMain.handle('get-developer-token', () => getDeveloperToken());
-  ipcMain.handle('refresh-developer-token', () => refreshDeveloperToken());
-  ipcMain.handle('is-musickit-configured', () => isMusicKitConfigured());
+  handleCommand('get-developer-token', () => getDeveloperToken());
+  handleCommand('refresh-developer-token', () => refreshDeveloperToken());
+  handleCommand('get-developer-token-expiry', () => getDeveloperTokenExpiry());
+  handleCommand('is-musickit-configured', () => isMusicKitConfigured());
+  handleCommand('test-developer-token', () => testDeveloperToken());
+  handleCommand('get-signing-key-fingerprint', () => getSigningKeyFingerprint());
+  handleCommand('rotate-private-key', (_event, keyContent: string, keyId: string) =>
+    rotatePrivateKey(keyContent, keyId),
+  );
+  handleCommand(
+    'set-musickit-config',
+    (_event, teamId: string, keyId: string, keyContent: string, allowPlaintext?: boolean) =>
+      setMusicKitConfig(teamId, keyId, keyContent, allowPlaintext === true),
+  );
+  handleCommand('get-auth-debug-enabled', () => getAuthDebugEnabled());
+  handleCommand('set-auth-debug-enabled', (_event, enabled: boolean) => setAuthDebugEnabled(enabled));
+  handleCommand('get-auth-debug-trace', () => getAuthDebugTrace());
+
+  // ── MusicKit ──────────────────────────────────────────────────────────────
+  handleCommand('get-musickit-init-config', () => getMusicKitInitConfig());
+  ipcMain.handle('set-content-rating-filter', (_event, mode: string) => setContentRatingFilter(mode));
+
+  // ── API ───────────────────────────────────────────────────────────────────
+  ipcMain.handle('get-api-base-url', () => getApiBaseUrl());
+  ipcMain.handle('set-api-base-url', (_event, url: string) => setApiBaseUrl(url));
 
   // ── Auth ──────────────────────────────────────────────────────────────────
-  ipcMain.handle('open-auth-window', (_event, authUrl: string) => {
-    const mainWindow = getMainWindow();
-    if (!mainWindow) throw new Error('Main window not found');
-    openAuthWindow(mainWindow, authUrl);
-  });
+  handleCommand('open-auth-window', (_event, authUrl: string) => openAuthWindow(requireMainWindow(), authUrl));
 
   // ── Window Management ─────────────────────────────────────────────────────
-  ipcMain.handle('open-mini-player', () => {
-    const mainWindow = getMainWindow();
-    if (!mainWindow) return;
+  handleCommand('open-mini-player', () => {
+    const mainWindow = requireMainWindow();
     if (isMiniPlayerMode) return;
 
     // Save current bounds
     savedBounds = mainWindow.getBounds();
     isMiniPlayerMode = true;
 
-    // Resize to mini player dimensions
-    mainWindow.setMinimumSize(280, 340);
-    mainWindow.setSize(280, 340);
+    // Resize to mini player dimensions, back where it was last time if that's still on screen
+    const { width, height } = MINI_PLAYER_SIZE;
+    mainWindow.setMinimumSize(width, height);
+    const bounds = resolveMiniPlayerBounds(
+      getMiniPlayerPosition(),
+      screen.getAllDisplays().map((display) => display.workArea),
+    );
+    if (bounds) {
+      mainWindow.setBounds(bounds);
+    } else {
+      mainWindow.setSize(width, height);
+      mainWindow.center();
+    }
     mainWindow.setResizable(false);
+    miniPlayerMovedListener = () => saveMiniPlayerPosition(mainWindow);
+    mainWindow.on('moved', miniPlayerMovedListener);
     mainWindow.setAlwaysOnTop(true);
     mainWindow.setSkipTaskbar(true);
+    applyFullscreenBehavior(mainWindow, getShowOverFullscreen());
 
     // Tell renderer to switch to mini player route
     mainWindow.webContents.send('enter-mini-player');
@@ -52,50 +209,157 @@ export function registerIpcHandlers(getMainWindow: () => BrowserWindow | null):
     mainWindow.focus();
   });
 
-  ipcMain.handle('close-mini-player', () => {
-    const mainWindow = getMainWindow();
-    if (!mainWindow || !isMiniPlayerMode) return;
+  handleCommand('close-mini-player', () => {
+    exitMiniPlayer(requireMainWindow());
+  });
 
-    isMiniPlayerMode = false;
+  handleCommand('get-mini-player-art-action', () => getMiniPlayerArtAction());
+  handleCommand('set-mini-player-art-action', (_event, action: string) => setMiniPlayerArtAction(action));
+  handleCommand('mini-player-art-clicked', () => {
+    const mainWindow = requireMainWindow();
+    return dispatchArtAction(getMiniPlayerArtAction(), {
+      openMain: () => exitMiniPlayer(mainWindow),
+      openLyrics: () => {
+        exitMiniPlayer(mainWindow);
+        mainWindow.webContents.send('open-lyrics');
+      },
+    });
+  });
 
-    // Restore window properties
-    mainWindow.setAlwaysOnTop(false);
-    mainWindow.setSkipTaskbar(false);
-    mainWindow.setResizable(true);
-    mainWindow.setMinimumSize(800, 600);
+  handleCommand('set-mini-player-fullscreen-behavior', (_event, showOverFullscreen: boolean) => {
+    setMiniPlayerFullscreenBehavior(showOverFullscreen, getMainWindow(), isMiniPlayerMode);
+  });
 
-    // Restore saved bounds
-    if (savedBounds) {
-      mainWindow.setBounds(savedBounds);
-      savedBounds = null;
-    } else {
-      mainWindow.setSize(1280, 800);
-      mainWindow.center();
-    }
+  handleCommand('set-window-min-size', (_event, label: string, width: number, height: number) => {
+    // The mini player manages its own size; the new minimum applies when it exits
+    return setWindowMinSize(label, width, height, !(label === 'main' && isMiniPlayerMode));
+  });
+
+  handleCommand('set-traffic-light-inset', (_event, label: string, x: number, y: number) =>
+    setTrafficLightInset(label, x, y),
+  );
 
-    // Tell renderer to switch back to main view
-    mainWindow.webContents.send('exit-mini-player');
+  handleCommand('frontend-ready', () => frontendReady());
+
+  handleCommand('hide-main-window', () => {
+    requireMainWindow().hide();
+  });
+
+  handleCommand('show-main-window', () => {
+    const mainWindow = requireMainWindow();
     mainWindow.show();
     mainWindow.focus();
   });
 
-  ipcMain.handle('hide-main-window', () => {
-    getMainWindow()?.hide();
-  });
+  // ── Discord ───────────────────────────────────────────────────────────────
+  handleCommand('discord-connect', (_event, appId: string) => discordConnect(appId));
+  handleCommand('discord-disconnect', () => discordDisconnect());
+  handleCommand('discord-set-activity', (_event, params) => discordSetActivity(params));
+  handleCommand('discord-clear-activity', () => discordClearActivity());
+  handleCommand('get-discord-pause-grace', () => getPauseGraceSeconds());
+  handleCommand('set-discord-pause-grace', (_event, seconds: number) => setPauseGraceSeconds(seconds));
+  handleCommand('get-discord-show-while-paused', () => getShowWhilePaused());
+  handleCommand('set-discord-show-while-paused', (_event, enabled: boolean) => setShowWhilePaused(enabled));
 
-  ipcMain.handle('show-main-window', () => {
-    const mainWindow = getMainWindow();
-    if (mainWindow) {
-      mainWindow.show();
-      mainWindow.focus();
+  // ── Setup ─────────────────────────────────────────────────────────────────
+  ipcMain.handle('complete-setup', () => completeSetup());
+
+  // ── Events ────────────────────────────────────────────────────────────────
+  ipcMain.handle('replay-events', () => replayEvents());
+
+  // ── Health ────────────────────────────────────────────────────────────────
+  ipcMain.handle('start-health-server', (_event, port: number) => startHealthServer(port));
+  ipcMain.handle('stop-health-server', () => stopHealthServer());
+
+  // ── Remote Control ────────────────────────────────────────────────────────
+  handleCommand('start-remote-control', (_event, port?: number) => startRemoteControl(port));
+  handleCommand('stop-remote-control', () => stopRemoteControl());
+
+  // ── Branding ──────────────────────────────────────────────────────────────
+  ipcMain.handle('get-branding', () => getBranding());
+
+  // ── Playback ──────────────────────────────────────────────────────────────
+  ipcMain.handle('check-playback-prerequisites', () => checkPlaybackPrerequisites());
+  ipcMain.handle('set-player-state', (event, state: PlayerStateReport) => {
+    setPlayerState(state);
+    emitToAll('player-state', getPlayerState(), { exclude: event.sender });
+
+    const queue = getQueue();
+    const index = state.trackId ? queue.findIndex((item) => item.id === state.trackId) : -1;
+    const nextIndex = index !== -1 && index + 1 < queue.length ? index + 1 : null;
+    const boundaries = checkTrackBoundaries(state.trackId, state.positionMs, state.durationMs, nextIndex);
+    for (const { channel, payload } of boundaries) {
+      emitToAll(channel, payload);
     }
   });
+  ipcMain.handle('get-player-state', () => getPlayerState());
+  ipcMain.handle('request-seek', (_event, positionMs: number) => requestSeek(positionMs));
+  ipcMain.handle('get-playback-snapshot', () => getPlaybackSnapshot());
+  ipcMain.handle('set-position-save-interval', (_event, seconds: number) => setPositionSaveInterval(seconds));
+  ipcMain.handle('apply-queue-update', (_event, update: QueueUpdate) => applyQueueUpdate(update));
+  ipcMain.handle('get-queue', () => getQueue());
+  ipcMain.handle('set-track-durations', (_event, durations: Record<string, number>) => setTrackDurations(durations));
+  ipcMain.handle('get-crossfade-window', (_event, trackId: string) => getCrossfadeWindow(trackId));
+  ipcMain.handle('get-crossfade-seconds', () => getCrossfadeSeconds());
+  ipcMain.handle('set-crossfade-seconds', (_event, seconds: number) => setCrossfadeSeconds(seconds));
+  handleCommand('scrobble-eligibility', (_event, trackDurationMs: number, positionMs: number) =>
+    scrobbleEligibility(trackDurationMs, positionMs));
+  handleCommand('get-scrobble-full-only', () => getScrobbleFullOnly());
+  handleCommand('set-scrobble-full-only', (_event, enabled: boolean) => setScrobbleFullOnly(enabled));
+  ipcMain.handle('get-autoplay', () => getAutoplay());
+  ipcMain.handle('set-autoplay', (_event, enabled: boolean) => setAutoplay(enabled));
+  ipcMain.handle('report-queue-ended', (_event, lastTrackId: string | null) => reportQueueEnded(lastTrackId));
+  ipcMain.handle('get-playback-modes', () => getPlaybackModes());
+  ipcMain.handle('set-playback-modes', (_event, shuffle: boolean, repeat: RepeatMode) => {
+    const modes = setPlaybackModes(shuffle, repeat);
+    emitToAll('player-state', getPlayerState());
+    return modes;
+  });
 
-  // ── Discord ───────────────────────────────────────────────────────────────
-  ipcMain.handle('discord-connect', () => discordConnect());
-  ipcMain.handle('discord-disconnect', () => discordDisconnect());
-  ipcMain.handle('discord-set-activity', (_event, params) => discordSetActivity(params));
-  ipcMain.handle('discord-clear-activity', () => discordClearActivity());
+  // ── Artwork ───────────────────────────────────────────────────────────────
+  ipcMain.handle('get-cached-artwork', (_event, url: string | null, size?: number) => getCachedArtwork(url, size));
+  handleCommand('resolve-artwork-url', (_event, url: string, size?: number) => resolveArtworkUrl(url, size));
+  handleCommand('get-artwork-quality', () => ({
+    preferred: getArtworkQuality(),
+    effective: getEffectiveArtworkQuality(),
+  }));
+  handleCommand('set-artwork-quality', (_event, quality: string) => setArtworkQuality(quality));
+  handleCommand('set-network-metered', (_event, metered: boolean) => setNetworkMetered(metered));
+  ipcMain.handle('set-fallback-artwork', (_event, path: string | null) => setFallbackArtwork(path));
+  ipcMain.handle('repair-artwork-cache', () => repairArtworkCache());
+  handleCommand('cache-usage', () => getCacheUsage());
+  handleCommand('set-cache-budget', (_event, bytes: number) => setCacheBudget(bytes));
+  ipcMain.handle('prefetch-palettes', (_event, urls: string[]) => prefetchPalettes(urls));
+  ipcMain.handle('set-active-artwork', (_event, url: string) => setActiveArtwork(url));
+  ipcMain.handle('get-active-palette', () => getActivePalette());
+
+  // ── Library ───────────────────────────────────────────────────────────────
+  ipcMain.handle('cache-library-snapshot', (_event, items: LibrarySnapshotItem[]) => cacheLibrarySnapshot(items));
+  ipcMain.handle('get-library-snapshot', () => getLibrarySnapshot());
+  handleCommand('import-apple-music-export', (_event, path: string) => importAppleMusicExport(path));
+
+  // ── Audio Output ──────────────────────────────────────────────────────────
+  ipcMain.handle('list-output-devices', () => getOutputDevices());
+  ipcMain.handle('report-output-devices', (_event, devices: OutputDevice[], currentId: string | null) =>
+    reportOutputDevices(devices, currentId));
+
+  // ── Recap ─────────────────────────────────────────────────────────────────
+  ipcMain.handle('generate-listening-recap', (_event, trackArts: RecapTrack[], title: string) =>
+    generateListeningRecap(trackArts, title));
+
+  // ── Performance ───────────────────────────────────────────────────────────
+  ipcMain.handle('get-process-stats', () => getProcessStats());
+  ipcMain.handle('set-perf-overlay', (_event, enabled: boolean) => setPerfOverlay(enabled, getMainWindow));
+
+  // ── Telemetry ─────────────────────────────────────────────────────────────
+  ipcMain.handle('set-telemetry', (_event, enabled: boolean, endpoint?: string) => setTelemetry(enabled, endpoint));
+  ipcMain.handle('get-telemetry-status', () => getTelemetryStatus());
+  ipcMain.handle('record-telemetry-event', (_event, name: string, count?: number) =>
+    recordTelemetryEvent(name, count));
+
+  // ── Logging ───────────────────────────────────────────────────────────────
+  ipcMain.handle('frontend-log', (_event, level: string, message: string, context?: unknown) =>
+    frontendLog(level, message, context));
 
   // ── Settings ────────────────────────────────────────────────────────────
   ipcMain.handle('set-close-behavior', (_event, miniPlayer: boolean) => {
@@ -110,6 +374,9 @@ export function registerIpcHandlers(getMainWindow: () => BrowserWindow | null):
     return app.getLoginItemSettings().openAtLogin;
   });
 
+  ipcMain.handle('get-language', () => getLanguage());
+  ipcMain.handle('set-language', (_event, code: string | null) => setLanguage(code));
+
   // ── Global Shortcuts ──────────────────────────────────────────────────────
   ipcMain.handle('register-shortcut', (_event, accelerator: string) => {
     const mainWindow = getMainWindow();
@@ -132,7 +399,22 @@ export function registerIpcHandlers(getMainWindow: () => BrowserWindow | null):
     }
   });
 
+  handleCommand('set-shortcuts', (_event, bindings: Record<string, string>) => {
+    const mainWindow = requireMainWindow();
+    return setShortcuts(bindings, (accelerator) => {
+      mainWindow.webContents.send('global-shortcut-triggered', accelerator);
+    });
+  });
+
+  handleCommand('get-effective-shortcuts', () => getEffectiveShortcuts());
+
   ipcMain.handle('unregister-all-shortcuts', () => {
+    clearShortcuts();
     globalShortcut.unregisterAll();
+    // Media keys aren't part of the renderer's shortcuts; keep them bound
+    if (getMediaKeysEnabled()) registerMediaKeys();
   });
+
+  handleCommand('get-media-keys-enabled', () => getMediaKeysEnabled());
+  handleCommand('set-media-keys-enabled', (_event, enabled: boolean) => setMediaKeysEnabled(enabled));
 }
@@ -0,0 +1,56 @@
+// Compact snapshot of the user's library (albums/playlists) kept on disk so
+// the UI has something to show while offline.
+
+import { app } from 'electron';
+import { join } from 'path';
+import { existsSync, readFileSync, writeFileSync } from 'fs';
+
+export interface LibrarySnapshotItem {
+  id: string;
+  type: 'album' | 'playlist';
+  title: string;
+  artworkUrl?: string;
+}
+
+export interface LibrarySnapshot {
+  items: LibrarySnapshotItem[];
+  updated_at: number;
+}
+
+export const MAX_SNAPSHOT_ITEMS = 200;
+
+function snapshotPath(): string {
+  return join(app.getPath('userData'), 'library-snapshot.json');
+}
+
+export function buildLibrarySnapshot(items: LibrarySnapshotItem[], now: number = Date.now()): LibrarySnapshot {
+  const valid = items.filter((item) =>
+    item && typeof item.id === 'string' && typeof item.title === 'string' &&
+    (item.type === 'album' || item.type === 'playlist'));
+
+  return {
+    items: valid.slice(0, MAX_SNAPSHOT_ITEMS).map(({ id, type, title, artworkUrl }) => ({ id, type, title, artworkUrl })),
+    updated_at: now,
+  };
+}
+
+export function cacheLibrarySnapshot(items: LibrarySnapshotItem[]): LibrarySnapshot {
+  const snapshot = buildLibrarySnapshot(items);
+  try {
+    writeFileSync(snapshotPath(), JSON.stringify(snapshot));
+  } catch (error) {
+    throw new Error(`Failed to write library snapshot: ${error}`);
+  }
+  return snapshot;
+}
+
+export function getLibrarySnapshot(): LibrarySnapshot | null {
+  const path = snapshotPath();
+  if (!existsSync(path)) return null;
+
+  try {
+    return JSON.parse(readFileSync(path, 'utf-8')) as LibrarySnapshot;
+  } catch {
+    return null;
+  }
+}
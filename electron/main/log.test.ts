@@ -0,0 +1,66 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({ app: { getPath: () => '/tmp' } }));
+
+import { formatLogLine, mapLogLevel, RateLimiter } from './log';
+
+describe('mapLogLevel', () => {
+  it('maps console and common level names', () => {
+    expect(mapLogLevel('trace')).toBe('debug');
+    expect(mapLogLevel('log')).toBe('info');
+    expect(mapLogLevel('warning')).toBe('warn');
+    expect(mapLogLevel('fatal')).toBe('error');
+  });
+
+  it('ignores case', () => {
+    expect(mapLogLevel('ERROR')).toBe('error');
+  });
+
+  it('falls back to info for unknown or missing levels', () => {
+    expect(mapLogLevel('verbose')).toBe('info');
+    expect(mapLogLevel(undefined as unknown as string)).toBe('info');
+  });
+});
+
+describe('RateLimiter', () => {
+  const START = 100_000;
+
+  it('allows up to max messages per window', () => {
+    const limiter = new RateLimiter(2, 1_000);
+    expect(limiter.take(START).allowed).toBe(true);
+    expect(limiter.take(START + 1).allowed).toBe(true);
+    expect(limiter.take(START + 2).allowed).toBe(false);
+  });
+
+  it('reports the dropped count once when the next window opens', () => {
+    const limiter = new RateLimiter(1, 1_000);
+    limiter.take(START);
+    limiter.take(START + 1);
+    limiter.take(START + 2);
+
+    expect(limiter.take(START + 1_000)).toEqual({ allowed: true, droppedBefore: 2 });
+    expect(limiter.take(START + 2_000)).toEqual({ allowed: true, droppedBefore: 0 });
+  });
+});
+
+describe('formatLogLine', () => {
+  const now = new Date('2026-01-02T03:04:05.000Z');
+
+  it('formats level, source and message', () => {
+    expect(formatLogLine('warn', 'frontend', 'hello', undefined, now)).toBe(
+      '[2026-01-02T03:04:05.000Z] [WARN] source=frontend hello\n',
+    );
+  });
+
+  it('appends context as JSON', () => {
+    expect(formatLogLine('info', 'backend', 'hi', { a: 1 }, now)).toBe(
+      '[2026-01-02T03:04:05.000Z] [INFO] source=backend hi | {"a":1}\n',
+    );
+  });
+
+  it('survives context that cannot be serialized', () => {
+    const circular: Record<string, unknown> = {};
+    circular.self = circular;
+    expect(formatLogLine('info', 'backend', 'hi', circular, now)).toContain('| [object Object]');
+  });
+});
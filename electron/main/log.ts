@@ -0,0 +1,128 @@
+// Main-process log file sink.
+// Backend messages (source=backend, captured from the main process's console)
+// and forwarded renderer logs (source=frontend) share one file under the OS
+// logs directory so user reports carry both sides.
+
+import { app } from 'electron';
+import { join } from 'path';
+import { format } from 'util';
+import { createWriteStream, mkdirSync, WriteStream } from 'fs';
+
+export type LogLevel = 'debug' | 'info' | 'warn' | 'error';
+
+const LEVEL_ALIASES: Record<string, LogLevel> = {
+  trace: 'debug',
+  debug: 'debug',
+  log: 'info',
+  info: 'info',
+  warn: 'warn',
+  warning: 'warn',
+  error: 'error',
+  fatal: 'error',
+};
+
+// Frontend logs: at most RATE_LIMIT_MAX messages per RATE_LIMIT_WINDOW_MS
+const RATE_LIMIT_MAX = 50;
+const RATE_LIMIT_WINDOW_MS = 10_000;
+
+export function mapLogLevel(level: string): LogLevel {
+  return LEVEL_ALIASES[level?.toLowerCase()] ?? 'info';
+}
+
+export class RateLimiter {
+  private windowStart = 0;
+  private count = 0;
+  private dropped = 0;
+
+  constructor(private readonly max: number, private readonly windowMs: number) {}
+
+  /** Returns whether the message may be written, and how many were dropped
+   *  in the previous window (reported once when a new window opens). */
+  take(now: number = Date.now()): { allowed: boolean; droppedBefore: number } {
+    let droppedBefore = 0;
+    if (now - this.windowStart >= this.windowMs) {
+      droppedBefore = this.dropped;
+      this.windowStart = now;
+      this.count = 0;
+      this.dropped = 0;
+    }
+
+    if (this.count < this.max) {
+      this.count++;
+      return { allowed: true, droppedBefore };
+    }
+    this.dropped++;
+    return { allowed: false, droppedBefore };
+  }
+}
+
+// Appends are buffered by the stream and written off the main thread
+let stream: WriteStream | null = null;
+
+function logStream(): WriteStream {
+  if (!stream) {
+    const dir = app.getPath('logs');
+    mkdirSync(dir, { recursive: true });
+    stream = createWriteStream(join(dir, 'tuffahi.log'), { flags: 'a' });
+    stream.on('error', () => {
+      // Logging must never take the app down
+    });
+  }
+  return stream;
+}
+
+export function formatLogLine(level: LogLevel, source: string, message: string, context?: unknown, now = new Date()): string {
+  const ctx = context === undefined ? '' : ` | ${safeStringify(context)}`;
+  return `[${now.toISOString()}] [${level.toUpperCase()}] source=${source} ${message}${ctx}\n`;
+}
+
+export function writeLog(level: LogLevel, source: string, message: string, context?: unknown): void {
+  try {
+    logStream().write(formatLogLine(level, source, message, context));
+  } catch {
+    // Logging must never take the app down
+  }
+}
+
+/** Flushes buffered lines; call once at quit. */
+export function closeLog(): void {
+  stream?.end();
+  stream = null;
+}
+
+const CONSOLE_METHODS = ['debug', 'log', 'info', 'warn', 'error'] as const;
+let consoleCaptured = false;
+
+/** Tees the main process's console into the log file as source=backend. */
+export function captureBackendLogs(): void {
+  if (consoleCaptured) return;
+  consoleCaptured = true;
+
+  for (const method of CONSOLE_METHODS) {
+    const original = console[method].bind(console);
+    console[method] = (...args: unknown[]) => {
+      original(...args);
+      writeLog(mapLogLevel(method), 'backend', format(...args));
+    };
+  }
+}
+
+function safeStringify(value: unknown): string {
+  try {
+    return JSON.stringify(value);
+  } catch {
+    return String(value);
+  }
+}
+
+const frontendLimiter = new RateLimiter(RATE_LIMIT_MAX, RATE_LIMIT_WINDOW_MS);
+
+export function frontendLog(level: string, message: string, context?: unknown): void {
+  const { allowed, droppedBefore } = frontendLimiter.take();
+  if (droppedBefore > 0) {
+    writeLog('warn', 'frontend', `Rate limit dropped ${droppedBefore} frontend log messages`);
+  }
+  if (!allowed) return;
+
+  writeLog(mapLogLevel(level), 'frontend', String(message), context);
+}
@@ -0,0 +1,14 @@
+// Playback commands from the main process (seek, LAN remote) to the renderer's player.
+// MusicKit lives in the main window, so that's the only recipient.
+
+import { requireWindow } from './windows';
+
+export type MediaCommand =
+  | { type: 'Seek'; position_ms: number }
+  | { type: 'PlayPause' }
+  | { type: 'Next' }
+  | { type: 'Previous' };
+
+export function sendMediaCommand(command: MediaCommand): void {
+  requireWindow('main').webContents.send('media-command', command);
+}
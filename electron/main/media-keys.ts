@@ -0,0 +1,76 @@
+// System media keys, bound in the main process from startup so they work
+// before (and without) the renderer's shortcut map. They emit the same
+// events as the tray menu. Users can turn them off to leave the keys to
+// another player.
+
+import { globalShortcut } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import { getWindowByLabel } from './windows';
+
+// accelerator → tray event
+export const MEDIA_KEYS: Record<string, string> = {
+  MediaPlayPause: 'tray-play-pause',
+  MediaTrackNext: 'tray-next',
+  MediaTrackPrevious: 'tray-previous',
+};
+
+const bound = new Set<string>();
+
+/** Media keys belong to this module; the shortcut map never registers them itself. */
+export function isMediaKey(accelerator: string): boolean {
+  return Object.keys(MEDIA_KEYS).some((key) => key.toLowerCase() === accelerator.trim().toLowerCase());
+}
+
+export function isMediaKeyBound(accelerator: string): boolean {
+  return [...bound].some((key) => key.toLowerCase() === accelerator.trim().toLowerCase());
+}
+
+/**
+ * Binds every media key it can. A key another app already grabbed (common
+ * on Linux) is logged and skipped. Returns the keys that failed.
+ */
+export function registerMediaKeys(): { failed: string[] } {
+  unregisterMediaKeys();
+
+  const failed: string[] = [];
+  for (const [accelerator, channel] of Object.entries(MEDIA_KEYS)) {
+    let ok = false;
+    try {
+      ok = globalShortcut.register(accelerator, () => {
+        getWindowByLabel('main')?.webContents.send(channel);
+      });
+    } catch {
+      // Unsupported on this platform
+    }
+    if (ok) {
+      bound.add(accelerator);
+    } else {
+      failed.push(accelerator);
+    }
+  }
+
+  if (failed.length > 0) {
+    console.log(`[TUFFAHI] Could not register media keys: ${failed.join(', ')}`);
+  }
+  return { failed };
+}
+
+export function unregisterMediaKeys(): void {
+  for (const accelerator of bound) {
+    globalShortcut.unregister(accelerator);
+  }
+  bound.clear();
+}
+
+export function getMediaKeysEnabled(): boolean {
+  return getStoreValue<boolean>('mediaKeysEnabled') ?? true;
+}
+
+export function setMediaKeysEnabled(enabled: boolean): { enabled: boolean; failed: string[] } {
+  setStoreValue('mediaKeysEnabled', enabled);
+  if (!enabled) {
+    unregisterMediaKeys();
+    return { enabled, failed: [] };
+  }
+  return { enabled, ...registerMediaKeys() };
+}
@@ -0,0 +1,134 @@
+// Mini player window preferences and remembered position.
+
+import type { BrowserWindow } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import { clampToMonitors, overlapsAnyMonitor, Point, Rect } from './geometry';
+
+// ── Position ────────────────────────────────────────────────────────────────
+
+// The mini player isn't resizable, so only its position is remembered
+export const MINI_PLAYER_SIZE = { width: 280, height: 340 };
+
+export function getMiniPlayerPosition(): Point | null {
+  const stored = getStoreValue<Point>('miniPlayerPosition');
+  return stored && Number.isFinite(stored.x) && Number.isFinite(stored.y) ? stored : null;
+}
+
+export function saveMiniPlayerPosition(window: BrowserWindow): void {
+  const [x, y] = window.getPosition();
+  setStoreValue('miniPlayerPosition', { x, y });
+}
+
+/**
+ * Where to reopen the mini player. A position left partly off screen is
+ * pulled back in; one that's on no connected display (or missing) gives
+ * null, meaning center it.
+ */
+export function resolveMiniPlayerBounds(saved: Point | null, monitors: Rect[]): Rect | null {
+  if (!saved) return null;
+  const rect = { ...saved, ...MINI_PLAYER_SIZE };
+  if (!overlapsAnyMonitor(rect, monitors)) return null;
+  return clampToMonitors(rect, monitors);
+}
+
+// ── Fullscreen behavior (macOS) ─────────────────────────────────────────────
+
+export interface FullscreenBehavior {
+  visibleOnAllWorkspaces: boolean;
+  visibleOnFullScreen: boolean;
+  alwaysOnTopLevel: 'floating' | 'screen-saver';
+}
+
+/**
+ * Picks the window collection behavior for the mini player.
+ * `visibleOnFullScreen` maps to NSWindowCollectionBehaviorFullScreenAuxiliary and
+ * all-workspaces to CanJoinAllSpaces; only macOS has these concepts.
+ */
+export function selectFullscreenBehavior(
+  showOverFullscreen: boolean,
+  platform: NodeJS.Platform = process.platform,
+): FullscreenBehavior | null {
+  if (platform !== 'darwin') return null;
+
+  return showOverFullscreen
+    ? { visibleOnAllWorkspaces: true, visibleOnFullScreen: true, alwaysOnTopLevel: 'screen-saver' }
+    : { visibleOnAllWorkspaces: false, visibleOnFullScreen: false, alwaysOnTopLevel: 'floating' };
+}
+
+export function getShowOverFullscreen(): boolean {
+  return getStoreValue<boolean>('miniPlayerShowOverFullscreen') ?? false;
+}
+
+export function applyFullscreenBehavior(window: BrowserWindow, showOverFullscreen: boolean): void {
+  const behavior = selectFullscreenBehavior(showOverFullscreen);
+  if (!behavior) return;
+
+  window.setVisibleOnAllWorkspaces(behavior.visibleOnAllWorkspaces, {
+    visibleOnFullScreen: behavior.visibleOnFullScreen,
+  });
+  window.setAlwaysOnTop(true, behavior.alwaysOnTopLevel);
+}
+
+export function resetFullscreenBehavior(window: BrowserWindow): void {
+  if (process.platform !== 'darwin') return;
+  window.setVisibleOnAllWorkspaces(false);
+}
+
+export function setMiniPlayerFullscreenBehavior(
+  showOverFullscreen: boolean,
+  window: BrowserWindow | null,
+  isMiniPlayerMode: boolean,
+): void {
+  setStoreValue('miniPlayerShowOverFullscreen', showOverFullscreen);
+  if (process.platform !== 'darwin') {
+    console.log('[TUFFAHI] Mini player fullscreen behavior only applies on macOS; saved for later');
+    return;
+  }
+  if (window && isMiniPlayerMode) {
+    applyFullscreenBehavior(window, showOverFullscreen);
+  }
+}
+
+// ── Album art click ─────────────────────────────────────────────────────────
+
+export type MiniPlayerArtAction = 'openMain' | 'openLyrics' | 'none';
+
+const ART_ACTIONS: readonly MiniPlayerArtAction[] = ['openMain', 'openLyrics', 'none'];
+const DEFAULT_ART_ACTION: MiniPlayerArtAction = 'openMain';
+
+export function isMiniPlayerArtAction(value: unknown): value is MiniPlayerArtAction {
+  return ART_ACTIONS.includes(value as MiniPlayerArtAction);
+}
+
+export function getMiniPlayerArtAction(): MiniPlayerArtAction {
+  const stored = getStoreValue<string>('miniPlayerArtAction');
+  return isMiniPlayerArtAction(stored) ? stored : DEFAULT_ART_ACTION;
+}
+
+export function setMiniPlayerArtAction(action: string): MiniPlayerArtAction {
+  if (!isMiniPlayerArtAction(action)) {
+    throw new Error(`Invalid mini player art action: ${action} (expected ${ART_ACTIONS.join(', ')})`);
+  }
+  setStoreValue('miniPlayerArtAction', action);
+  return action;
+}
+
+export interface ArtActionHandlers {
+  openMain: () => void;
+  openLyrics: () => void;
+}
+
+/** Runs the configured action for an art click; returns the action taken. */
+export function dispatchArtAction(action: MiniPlayerArtAction, handlers: ArtActionHandlers): MiniPlayerArtAction {
+  switch (action) {
+    case 'openMain':
+      handlers.openMain();
+      break;
+    case 'openLyrics':
+      handlers.openLyrics();
+      break;
+    case 'none':
+      break;
+  }
+  return action;
+}
@@ -0,0 +1,58 @@
+// Everything the renderer needs to call MusicKit.configure(), in one place.
+// MusicKit JS enforces the content restriction; we only own the preference.
+
+import { app } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import { getDeveloperToken } from './token';
+import { emitToAll } from './events';
+import { getBranding } from './branding';
+
+export type ContentRatingFilter = 'explicit' | 'clean';
+
+export interface MusicKitInitConfig {
+  developerToken: string;
+  app: { name: string; build: string };
+  contentRating: ContentRatingFilter;
+}
+
+const DEFAULT_CONTENT_RATING: ContentRatingFilter = 'explicit';
+
+export function isContentRatingFilter(value: unknown): value is ContentRatingFilter {
+  return value === 'explicit' || value === 'clean';
+}
+
+export function getContentRatingFilter(): ContentRatingFilter {
+  const stored = getStoreValue<string>('contentRatingFilter');
+  return isContentRatingFilter(stored) ? stored : DEFAULT_CONTENT_RATING;
+}
+
+export function setContentRatingFilter(mode: string): ContentRatingFilter {
+  if (!isContentRatingFilter(mode)) {
+    throw new Error(`Invalid content rating filter: ${mode} (expected "explicit" or "clean")`);
+  }
+  setStoreValue('contentRatingFilter', mode);
+  emitToAll('content-filter-changed', mode);
+  return mode;
+}
+
+export function buildMusicKitInitConfig(
+  developerToken: string,
+  appName: string,
+  build: string,
+  contentRating: ContentRatingFilter,
+): MusicKitInitConfig {
+  return {
+    developerToken,
+    app: { name: appName, build },
+    contentRating,
+  };
+}
+
+export async function getMusicKitInitConfig(): Promise<MusicKitInitConfig> {
+  return buildMusicKitInitConfig(
+    await getDeveloperToken(),
+    getBranding().appName,
+    app.getVersion(),
+    getContentRatingFilter(),
+  );
+}
@@ -0,0 +1,83 @@
+// Audio output devices, as reported by the renderer.
+// Chromium only exposes sinks to the page (enumerateDevices/setSinkId), so the
+// renderer reports the list here and the tray turns it into a submenu.
+//
+// Any change to the list or the selected device is broadcast as
+// `audio-route-changed`, and the tray rebuilds on it. Without a main window
+// nothing can enumerate or switch sinks, so the list is cleared when it goes
+// away rather than leaving the tray showing devices it can't act on.
+
+import type { MenuItemConstructorOptions } from 'electron';
+import { emitToAll } from './events';
+
+export interface OutputDevice {
+  id: string;
+  label: string;
+}
+
+let devices: OutputDevice[] = [];
+let currentDeviceId: string | null = null;
+const listeners = new Set<() => void>();
+
+export function getOutputDevices(): { devices: OutputDevice[]; currentDeviceId: string | null } {
+  return { devices, currentDeviceId };
+}
+
+/** Whether the device list or the selected device differs between two reports. */
+export function isRouteChange(
+  previous: OutputDevice[],
+  previousCurrent: string | null,
+  next: OutputDevice[],
+  nextCurrent: string | null,
+): boolean {
+  if (previousCurrent !== nextCurrent || previous.length !== next.length) return true;
+  return previous.some((device, i) => device.id !== next[i].id || device.label !== next[i].label);
+}
+
+function updateRoute(list: OutputDevice[], current: string | null): void {
+  if (!isRouteChange(devices, currentDeviceId, list, current)) return;
+  devices = list;
+  currentDeviceId = current;
+  emitToAll('audio-route-changed', { devices, current_device_id: currentDeviceId });
+  listeners.forEach((listener) => listener());
+}
+
+export function reportOutputDevices(list: OutputDevice[], current: string | null): void {
+  updateRoute(list, current);
+}
+
+export function setCurrentOutputDevice(id: string): void {
+  updateRoute(devices, id);
+}
+
+/** Forgets the reported devices; called when the window that reports them is destroyed. */
+export function clearOutputDevices(): void {
+  updateRoute([], null);
+}
+
+/** Runs on every audio-route-changed broadcast. */
+export function onOutputDevicesChanged(listener: () => void): () => void {
+  listeners.add(listener);
+  return () => listeners.delete(listener);
+}
+
+export function buildOutputDeviceSubmenu(
+  list: OutputDevice[],
+  current: string | null,
+  onSelect: (id: string) => void,
+  emptyLabel: string = 'No output devices available',
+): MenuItemConstructorOptions[] {
+  if (list.length === 0) {
+    return [{ label: emptyLabel, enabled: false }];
+  }
+
+  // The "default" sink is selected when nothing explicit was chosen
+  const selected = current ?? 'default';
+
+  return list.map((device) => ({
+    label: device.label,
+    type: 'radio' as const,
+    checked: device.id === selected,
+    click: () => onSelect(device.id),
+  }));
+}
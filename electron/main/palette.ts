@@ -0,0 +1,126 @@
+// Artwork color palettes for theming the UI around the current track.
+// Palettes are computed from the on-disk artwork cache and memoized by URL,
+// so upcoming tracks can be prefetched and switch instantly.
+
+import { nativeImage } from 'electron';
+import { getCachedArtwork } from './artwork-cache';
+import { emitToAll } from './events';
+
+export interface Palette {
+  dominant: string;
+  secondary: string;
+}
+
+const SAMPLE_SIZE = 50;
+// Concurrent downloads/decodes during a prefetch
+const PREFETCH_CONCURRENCY = 3;
+const MAX_CACHED_PALETTES = 100;
+const DEFAULT_PALETTE: Palette = { dominant: 'rgb(30, 30, 30)', secondary: 'rgb(20, 20, 20)' };
+
+// url → palette, oldest first
+const palettes = new Map<string, Palette>();
+const inFlight = new Map<string, Promise<Palette>>();
+let activePalette: Palette | null = null;
+
+/**
+ * Most common quantized colors in a BGRA bitmap, skipping near-black and
+ * near-white pixels. Same approach as the renderer's AnimatedBackground.
+ */
+export function extractPalette(bitmap: Buffer): Palette {
+  const counts = new Map<string, number>();
+  // Every 4th pixel is plenty at 50x50
+  for (let i = 0; i + 2 < bitmap.length; i += 16) {
+    const b = bitmap[i];
+    const g = bitmap[i + 1];
+    const r = bitmap[i + 2];
+    const brightness = (r + g + b) / 3;
+    if (brightness < 30 || brightness > 230) continue;
+
+    const key = [r, g, b].map((c) => Math.min(255, Math.round(c / 32) * 32)).join(',');
+    counts.set(key, (counts.get(key) ?? 0) + 1);
+  }
+
+  const [first, second] = [...counts.entries()]
+    .sort((a, b) => b[1] - a[1])
+    .map(([key]) => `rgb(${key.split(',').join(', ')})`);
+  if (!first) return DEFAULT_PALETTE;
+  return { dominant: first, secondary: second ?? first };
+}
+
+function remember(url: string, palette: Palette): void {
+  palettes.delete(url);
+  palettes.set(url, palette);
+  if (palettes.size > MAX_CACHED_PALETTES) {
+    const oldest = palettes.keys().next().value;
+    if (oldest !== undefined) palettes.delete(oldest);
+  }
+}
+
+async function computePalette(url: string): Promise<Palette> {
+  const path = await getCachedArtwork(url);
+  const image = nativeImage.createFromPath(path);
+  if (image.isEmpty()) {
+    throw new Error(`Artwork is not a readable image: ${url}`);
+  }
+  return extractPalette(image.resize({ width: SAMPLE_SIZE, height: SAMPLE_SIZE }).toBitmap());
+}
+
+export function getCachedPalette(url: string): Palette | null {
+  return palettes.get(url) ?? null;
+}
+
+/** Returns the palette for `url`, reusing a cached or in-progress extraction. */
+export function getPalette(url: string): Promise<Palette> {
+  const cached = palettes.get(url);
+  if (cached) return Promise.resolve(cached);
+
+  let pending = inFlight.get(url);
+  if (!pending) {
+    pending = computePalette(url)
+      .then((palette) => {
+        remember(url, palette);
+        return palette;
+      })
+      .finally(() => inFlight.delete(url));
+    inFlight.set(url, pending);
+  }
+  return pending;
+}
+
+/** URLs from `urls` that still need extracting, without duplicates. */
+export function selectUncached(urls: string[], isKnown: (url: string) => boolean): string[] {
+  return [...new Set(urls.filter(Boolean))].filter((url) => !isKnown(url));
+}
+
+/**
+ * Extracts palettes for upcoming artwork, a few at a time. Failures are
+ * logged and skipped. Returns how many new palettes were computed.
+ */
+export async function prefetchPalettes(urls: string[]): Promise<number> {
+  const queue = selectUncached(urls, (url) => palettes.has(url) || inFlight.has(url));
+  let computed = 0;
+
+  const worker = async (): Promise<void> => {
+    for (let url = queue.shift(); url !== undefined; url = queue.shift()) {
+      try {
+        await getPalette(url);
+        computed++;
+      } catch (error) {
+        console.warn('[TUFFAHI] Palette prefetch failed:', error);
+      }
+    }
+  };
+  await Promise.all(Array.from({ length: PREFETCH_CONCURRENCY }, worker));
+  return computed;
+}
+
+export function getActivePalette(): Palette | null {
+  return activePalette;
+}
+
+export async function setActiveArtwork(url: string): Promise<Palette> {
+  const palette = await getPalette(url);
+  activePalette = palette;
+  emitToAll('theme-palette-changed', palette);
+  return palette;
+}
@@ -0,0 +1,88 @@
+// Protected-content (Widevine/EME) capability probe.
+// Lets the setup flow warn before playback silently fails on systems
+// where the CDM could not be installed.
+
+import * as electron from 'electron';
+
+const WIDEVINE_COMPONENT_ID = 'oimompecagnajdejgnnjijobebaeigek';
+
+export interface PlaybackPrerequisites {
+  eme_available: boolean;
+  webkit_version: string;
+  notes: string[];
+}
+
+export interface CapabilityFlags {
+  platform: NodeJS.Platform;
+  componentsApi: boolean;
+  widevineStatus: string | null;
+  widevineVersion: string | null;
+  isWayland: boolean;
+}
+
+export function assemblePlaybackNotes(flags: CapabilityFlags): string[] {
+  const notes: string[] = [];
+
+  if (!flags.componentsApi) {
+    notes.push('This build does not include the Widevine component updater; protected playback may not work on this system.');
+    return notes;
+  }
+
+  if (!flags.widevineStatus) {
+    notes.push('Widevine CDM is not installed; protected playback may not work on this system.');
+  } else if (flags.widevineStatus !== 'updated' && flags.widevineStatus !== 'installed') {
+    notes.push(`Widevine CDM is not ready (status: ${flags.widevineStatus}); protected playback may not work yet.`);
+  } else if (flags.widevineVersion) {
+    notes.push(`Widevine CDM ${flags.widevineVersion} is available.`);
+  }
+
+  if (flags.platform === 'linux') {
+    notes.push('On Linux only software-secure decryption is available; some content may be limited to lower quality.');
+    if (flags.isWayland) {
+      notes.push('Running under Wayland; if playback fails, try launching with --ozone-platform=x11.');
+    }
+  }
+
+  return notes;
+}
+
+export function isEmeAvailable(flags: CapabilityFlags): boolean {
+  return flags.componentsApi && (flags.widevineStatus === 'updated' || flags.widevineStatus === 'installed');
+}
+
+async function probeCapabilities(): Promise<CapabilityFlags> {
+  const components = (electron as any).components;
+  let widevineStatus: string | null = null;
+  let widevineVersion: string | null = null;
+
+  if (components) {
+    try {
+      await components.whenReady();
+    } catch {
+      // Component install failed — status below reflects that
+    }
+    const status = components.status?.() ?? {};
+    const widevine = status[WIDEVINE_COMPONENT_ID];
+    if (widevine) {
+      widevineStatus = widevine.status ?? null;
+      widevineVersion = widevine.version ?? null;
+    }
+  }
+
+  return {
+    platform: process.platform,
+    componentsApi: !!components,
+    widevineStatus,
+    widevineVersion,
+    isWayland: process.platform === 'linux' && !!process.env.WAYLAND_DISPLAY,
+  };
+}
+
+export async function checkPlaybackPrerequisites(): Promise<PlaybackPrerequisites> {
+  const flags = await probeCapabilities();
+  return {
+    eme_available: isEmeAvailable(flags),
+    webkit_version: process.versions.chrome ?? 'unknown',
+    notes: assemblePlaybackNotes(flags),
+  };
+}
@@ -0,0 +1,35 @@
+// Shuffle/repeat preferences, persisted independently of the queue so they
+// survive restarts and queue replacement.
+
+import { getStoreValue, setStoreValue } from './store';
+
+export type RepeatMode = 'off' | 'one' | 'all';
+
+export interface PlaybackModes {
+  shuffle: boolean;
+  repeat: RepeatMode;
+}
+
+const REPEAT_MODES: readonly RepeatMode[] = ['off', 'one', 'all'];
+const DEFAULT_MODES: PlaybackModes = { shuffle: false, repeat: 'off' };
+
+export function isRepeatMode(value: unknown): value is RepeatMode {
+  return typeof value === 'string' && (REPEAT_MODES as readonly string[]).includes(value);
+}
+
+export function getPlaybackModes(): PlaybackModes {
+  const stored = getStoreValue<Partial<PlaybackModes>>('playbackModes');
+  return {
+    shuffle: typeof stored?.shuffle === 'boolean' ? stored.shuffle : DEFAULT_MODES.shuffle,
+    repeat: isRepeatMode(stored?.repeat) ? stored.repeat : DEFAULT_MODES.repeat,
+  };
+}
+
+export function setPlaybackModes(shuffle: boolean, repeat: RepeatMode): PlaybackModes {
+  if (!isRepeatMode(repeat)) {
+    throw new Error(`Invalid repeat mode: ${repeat}`);
+  }
+  const modes: PlaybackModes = { shuffle: !!shuffle, repeat };
+  setStoreValue('playbackModes', modes);
+  return modes;
+}
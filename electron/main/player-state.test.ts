@@ -0,0 +1,88 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+const { store } = vi.hoisted(() => ({ store: new Map<string, unknown>() }));
+
+vi.mock('./store', () => ({
+  getStoreValue: (key: string) => store.get(key),
+  setStoreValue: (key: string, value: unknown) => store.set(key, value),
+}));
+
+vi.mock('./media-command', () => ({ sendMediaCommand: vi.fn() }));
+vi.mock('./playback-modes', () => ({ getPlaybackModes: () => ({ shuffle: false, repeat: 'none' }) }));
+
+import { getPlaybackSnapshot, PositionSaver, setPlayerState } from './player-state';
+
+describe('PositionSaver', () => {
+  it('saves the first report right away', () => {
+    expect(new PositionSaver(10_000).shouldSave(0, false)).toBe(true);
+  });
+
+  it('saves at most once per interval', () => {
+    const saver = new PositionSaver(10_000);
+    saver.shouldSave(0, false);
+    expect(saver.shouldSave(9_999, false)).toBe(false);
+    expect(saver.shouldSave(10_000, false)).toBe(true);
+    expect(saver.shouldSave(15_000, false)).toBe(false);
+  });
+
+  it('always saves when forced and restarts the interval', () => {
+    const saver = new PositionSaver(10_000);
+    saver.shouldSave(0, false);
+    expect(saver.shouldSave(1_000, true)).toBe(true);
+    expect(saver.shouldSave(10_000, false)).toBe(false);
+    expect(saver.shouldSave(11_000, false)).toBe(true);
+  });
+
+  it('applies a new interval to the next report', () => {
+    const saver = new PositionSaver(10_000);
+    saver.shouldSave(0, false);
+    saver.setInterval(2_000);
+    expect(saver.shouldSave(2_000, false)).toBe(true);
+  });
+});
+
+describe('setPlayerState snapshots', () => {
+  let now = 1_000_000;
+
+  function report(trackId: string | null, positionMs: number, isPlaying = true): void {
+    vi.setSystemTime(now);
+    setPlayerState({ trackId, positionMs, durationMs: 200_000, isPlaying });
+  }
+
+  beforeEach(() => {
+    vi.useFakeTimers();
+    now += 60_000;
+    report(null, 0, false);
+    store.clear();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('saves the exact position on pause', () => {
+    report('a', 0);
+    now += 1_000;
+    report('a', 1_500.7, false);
+    expect(getPlaybackSnapshot()).toMatchObject({ trackId: 'a', positionMs: 1_500 });
+  });
+
+  it('keeps the last position of the track when playback stops', () => {
+    report('a', 0);
+    now += 3_000;
+    report('a', 3_000);
+    now += 1_000;
+    report(null, 0, false);
+    expect(getPlaybackSnapshot()).toMatchObject({ trackId: 'a', positionMs: 3_000 });
+  });
+
+  it('keeps the last position of the previous track on a track change', () => {
+    report('a', 0);
+    now += 3_000;
+    report('a', 3_000);
+    now += 1_000;
+    report('b', 0);
+    // The new track's first report lands inside the interval of the forced save
+    expect(getPlaybackSnapshot()).toMatchObject({ trackId: 'a', positionMs: 3_000 });
+  });
+});
@@ -0,0 +1,131 @@
+// Player state reported by the renderer, plus the persisted playback snapshot
+// used to resume where the user left off.
+
+import { getStoreValue, setStoreValue } from './store';
+import { sendMediaCommand } from './media-command';
+import { getPlaybackModes, RepeatMode } from './playback-modes';
+
+// What the renderer reports
+export interface PlayerStateReport {
+  trackId: string | null;
+  positionMs: number;
+  durationMs: number;
+  isPlaying: boolean;
+}
+
+// What gets broadcast: the report plus shuffle/repeat, which main owns
+export interface PlayerState extends PlayerStateReport {
+  shuffle: boolean;
+  repeat: RepeatMode;
+}
+
+export interface PlaybackSnapshot {
+  trackId: string;
+  positionMs: number;
+  savedAt: number;
+}
+
+const MIN_SAVE_INTERVAL_SECONDS = 1;
+const DEFAULT_SAVE_INTERVAL_SECONDS = 10;
+
+let playerState: PlayerStateReport = { trackId: null, positionMs: 0, durationMs: 0, isPlaying: false };
+
+/**
+ * Debounces snapshot writes: at most one save per interval while playing,
+ * but pauses/stops and forced flushes always write immediately.
+ */
+export class PositionSaver {
+  private lastSavedAt = Number.NEGATIVE_INFINITY;
+
+  constructor(private intervalMs: number) {}
+
+  setInterval(intervalMs: number): void {
+    this.intervalMs = intervalMs;
+  }
+
+  shouldSave(now: number, force: boolean): boolean {
+    if (force || now - this.lastSavedAt >= this.intervalMs) {
+      this.lastSavedAt = now;
+      return true;
+    }
+    return false;
+  }
+}
+
+export function getPositionSaveInterval(): number {
+  return getStoreValue<number>('positionSaveIntervalSeconds') ?? DEFAULT_SAVE_INTERVAL_SECONDS;
+}
+
+let saver: PositionSaver | null = null;
+
+function getSaver(): PositionSaver {
+  if (!saver) saver = new PositionSaver(getPositionSaveInterval() * 1000);
+  return saver;
+}
+
+export function setPositionSaveInterval(seconds: number): number {
+  if (!Number.isFinite(seconds) || seconds < MIN_SAVE_INTERVAL_SECONDS) {
+    throw new Error(`Position save interval must be at least ${MIN_SAVE_INTERVAL_SECONDS}s`);
+  }
+  setStoreValue('positionSaveIntervalSeconds', seconds);
+  getSaver().setInterval(seconds * 1000);
+  return seconds;
+}
+
+function saveSnapshot(state: PlayerStateReport, force: boolean): void {
+  if (!state.trackId) return;
+  const now = Date.now();
+  if (!getSaver().shouldSave(now, force)) return;
+
+  const snapshot: PlaybackSnapshot = {
+    trackId: state.trackId,
+    positionMs: Math.floor(state.positionMs),
+    savedAt: now,
+  };
+  setStoreValue('playbackSnapshot', snapshot);
+}
+
+export function getPlayerState(): PlayerState {
+  return { ...playerState, ...getPlaybackModes() };
+}
+
+export function setPlayerState(state: PlayerStateReport): void {
+  const previous = playerState;
+  // A stop reports no track, so keep the last known position of the one
+  // that ended before it's replaced; same when switching tracks
+  if (previous.trackId && state.trackId !== previous.trackId) {
+    saveSnapshot(previous, true);
+  }
+  playerState = state;
+
+  // Pausing always persists the exact position
+  saveSnapshot(state, previous.isPlaying && !state.isPlaying);
+}
+
+export function clampSeekPosition(state: PlayerStateReport, positionMs: number): number {
+  if (!state.trackId) {
+    throw new Error('Cannot seek: no track is playing');
+  }
+  if (!Number.isFinite(positionMs)) {
+    throw new Error('Cannot seek: position must be a number');
+  }
+  if (state.durationMs <= 0) {
+    throw new Error('Cannot seek: track duration is unknown');
+  }
+  return Math.min(Math.max(0, Math.floor(positionMs)), state.durationMs);
+}
+
+/** Validates a tapped position against the current track and asks the player to seek there. */
+export function requestSeek(positionMs: number): number {
+  const clamped = clampSeekPosition(playerState, positionMs);
+  sendMediaCommand({ type: 'Seek', position_ms: clamped });
+  return clamped;
+}
+
+export function flushPlaybackSnapshot(): void {
+  saveSnapshot(playerState, true);
+}
+
+export function getPlaybackSnapshot(): PlaybackSnapshot | null {
+  return getStoreValue<PlaybackSnapshot>('playbackSnapshot') ?? null;
+}
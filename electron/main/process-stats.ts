@@ -0,0 +1,69 @@
+// Resource usage of the main process for the performance overlay.
+
+import { readFileSync } from 'fs';
+import type { BrowserWindow } from 'electron';
+
+export interface ProcessStats {
+  rss_bytes: number;
+  cpu_percent: number;
+  thread_count: number | null;
+}
+
+const DEFAULT_SAMPLE_MS = 250;
+const MIN_SAMPLE_MS = 100;
+const MAX_SAMPLE_MS = 2000;
+const OVERLAY_INTERVAL_MS = 2000;
+
+let overlayTimer: ReturnType<typeof setInterval> | null = null;
+
+/** Keeps the CPU sampling window long enough to be meaningful but short enough not to stall callers. */
+export function clampSampleInterval(ms: number | undefined): number {
+  if (ms === undefined || !Number.isFinite(ms)) return DEFAULT_SAMPLE_MS;
+  return Math.max(MIN_SAMPLE_MS, Math.min(MAX_SAMPLE_MS, Math.floor(ms)));
+}
+
+function readThreadCount(): number | null {
+  if (process.platform !== 'linux') return null;
+  try {
+    const match = readFileSync('/proc/self/status', 'utf-8').match(/^Threads:\s+(\d+)/m);
+    return match ? parseInt(match[1], 10) : null;
+  } catch {
+    return null;
+  }
+}
+
+export async function getProcessStats(sampleMs?: number): Promise<ProcessStats> {
+  const interval = clampSampleInterval(sampleMs);
+  const startUsage = process.cpuUsage();
+  const startTime = process.hrtime.bigint();
+
+  await new Promise((resolve) => setTimeout(resolve, interval));
+
+  const usage = process.cpuUsage(startUsage);
+  const elapsedMicros = Number(process.hrtime.bigint() - startTime) / 1000;
+  const cpuMicros = usage.user + usage.system;
+
+  return {
+    rss_bytes: process.memoryUsage().rss,
+    cpu_percent: elapsedMicros > 0 ? Math.round((cpuMicros / elapsedMicros) * 1000) / 10 : 0,
+    thread_count: readThreadCount(),
+  };
+}
+
+export function setPerfOverlay(enabled: boolean, getWindow: () => BrowserWindow | null): void {
+  if (overlayTimer) {
+    clearInterval(overlayTimer);
+    overlayTimer = null;
+  }
+  if (!enabled) return;
+
+  overlayTimer = setInterval(async () => {
+    const window = getWindow();
+    if (!window || window.isDestroyed()) return;
+    try {
+      window.webContents.send('process-stats', await getProcessStats());
+    } catch {
+      // Skip this tick
+    }
+  }, OVERLAY_INTERVAL_MS);
+}
@@ -0,0 +1,60 @@
+// Main-process mirror of the playback queue.
+// The renderer reports changes as diffs; other windows receive the same diff
+// via `queue-update` and late joiners call `get-queue` for the full list.
+
+import { emitToAll } from './events';
+
+export interface QueueItem {
+  id: string;
+  type: string;
+  title?: string;
+  artist?: string;
+}
+
+export type QueueUpdate =
+  | { kind: 'added'; items: QueueItem[]; at: number }
+  | { kind: 'removed'; indices: number[] }
+  | { kind: 'moved'; from: number; to: number }
+  | { kind: 'cleared' }
+  | { kind: 'replaced'; items: QueueItem[] };
+
+let queue: QueueItem[] = [];
+
+export function applyUpdate(current: QueueItem[], update: QueueUpdate): QueueItem[] {
+  switch (update.kind) {
+    case 'added': {
+      const at = Math.max(0, Math.min(update.at, current.length));
+      return [...current.slice(0, at), ...update.items, ...current.slice(at)];
+    }
+    case 'removed': {
+      const drop = new Set(update.indices);
+      return current.filter((_, i) => !drop.has(i));
+    }
+    case 'moved': {
+      const { from, to } = update;
+      if (from < 0 || from >= current.length || to < 0 || to >= current.length || from === to) {
+        return current;
+      }
+      const next = [...current];
+      const [item] = next.splice(from, 1);
+      next.splice(to, 0, item);
+      return next;
+    }
+    case 'cleared':
+      return [];
+    case 'replaced':
+      return [...update.items];
+    default:
+      throw new Error(`Unknown queue update: ${JSON.stringify(update)}`);
+  }
+}
+
+export function getQueue(): QueueItem[] {
+  return queue;
+}
+
+export function applyQueueUpdate(update: QueueUpdate): void {
+  queue = applyUpdate(queue, update);
+  // Late joiners need the whole queue, not the last diff
+  emitToAll('queue-update', update, { replayPayload: { kind: 'replaced', items: queue } });
+}
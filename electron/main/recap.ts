@@ -0,0 +1,125 @@
+// "Listening recap" image generation.
+// Downloads recent tracks' artwork and renders a shareable PNG grid with a
+// header in an offscreen window, then writes it to the temp directory.
+
+import { app, BrowserWindow, net } from 'electron';
+import { join } from 'path';
+import { writeFileSync } from 'fs';
+
+export interface RecapTrack {
+  url: string;
+  title: string;
+  artist: string;
+}
+
+export interface RecapLayout {
+  cols: number;
+  rows: number;
+  tileSize: number;
+  headerHeight: number;
+  width: number;
+  height: number;
+}
+
+const MAX_ITEMS = 25;
+const MAX_COLS = 5;
+const MAX_WIDTH = 1080;
+const MAX_TILE_SIZE = 360;
+const GAP = 16;
+const CAPTION_HEIGHT = 48;
+const HEADER_HEIGHT = 120;
+
+export function computeRecapLayout(count: number): RecapLayout {
+  const items = Math.max(1, Math.min(count, MAX_ITEMS));
+  // Near-square grids read best, but never wider than MAX_COLS
+  const cols = Math.min(MAX_COLS, Math.ceil(Math.sqrt(items)));
+  const rows = Math.ceil(items / cols);
+  const tileSize = Math.min(MAX_TILE_SIZE, Math.floor((MAX_WIDTH - GAP * (cols + 1)) / cols));
+  const width = tileSize * cols + GAP * (cols + 1);
+  const height = HEADER_HEIGHT + rows * (tileSize + CAPTION_HEIGHT + GAP) + GAP;
+
+  return { cols, rows, tileSize, headerHeight: HEADER_HEIGHT, width, height };
+}
+
+function escapeHtml(value: string): string {
+  return value
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
+
+async function downloadAsDataUrl(url: string): Promise<string | null> {
+  try {
+    const response = await net.fetch(url);
+    if (!response.ok) return null;
+    const type = response.headers.get('content-type') ?? 'image/jpeg';
+    const body = Buffer.from(await response.arrayBuffer());
+    return `data:${type};base64,${body.toString('base64')}`;
+  } catch {
+    return null;
+  }
+}
+
+function buildRecapHtml(title: string, tracks: RecapTrack[], arts: (string | null)[], layout: RecapLayout): string {
+  const tiles = tracks.map((track, i) => {
+    const col = i % layout.cols;
+    const row = Math.floor(i / layout.cols);
+    const left = GAP + col * (layout.tileSize + GAP);
+    const top = layout.headerHeight + row * (layout.tileSize + CAPTION_HEIGHT + GAP);
+    const art = arts[i]
+      ? `<img src="${arts[i]}" style="width:${layout.tileSize}px;height:${layout.tileSize}px">`
+      : `<div class="ph" style="width:${layout.tileSize}px;height:${layout.tileSize}px"></div>`;
+    return `<div class="t" style="left:${left}px;top:${top}px;width:${layout.tileSize}px">${art}`
+      + `<div class="n">${escapeHtml(track.title)}</div><div class="a">${escapeHtml(track.artist)}</div></div>`;
+  });
+
+  return `<!DOCTYPE html><html><head><meta charset="UTF-8"><style>
+*{margin:0;padding:0;box-sizing:border-box}
+body{width:${layout.width}px;height:${layout.height}px;background:#0a0a0a;font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;overflow:hidden}
+.h{position:absolute;left:${GAP}px;top:32px;color:#fff;font-size:36px;font-weight:700}
+.t{position:absolute}.t img,.ph{display:block;border-radius:8px;object-fit:cover}.ph{background:#1f1f1f}
+.n,.a{white-space:nowrap;overflow:hidden;text-overflow:ellipsis}
+.n{color:#fff;font-size:14px;font-weight:600;margin-top:8px}.a{color:rgba(255,255,255,.55);font-size:12px}
+</style></head><body><div class="h">${escapeHtml(title)}</div>${tiles.join('')}</body></html>`;
+}
+
+export async function generateListeningRecap(trackArts: RecapTrack[], title: string): Promise<string> {
+  const tracks = trackArts.slice(0, MAX_ITEMS);
+  if (tracks.length === 0) {
+    throw new Error('No tracks provided for recap');
+  }
+
+  const layout = computeRecapLayout(tracks.length);
+  const arts = await Promise.all(tracks.map((t) => (t.url ? downloadAsDataUrl(t.url) : null)));
+
+  const window = new BrowserWindow({
+    width: layout.width,
+    height: layout.height,
+    show: false,
+    frame: false,
+    webPreferences: {
+      offscreen: true,
+      nodeIntegration: false,
+      contextIsolation: true,
+      sandbox: true,
+    },
+  });
+
+  try {
+    const html = buildRecapHtml(title, tracks, arts, layout);
+    await window.loadURL(`data:text/html;charset=utf-8,${encodeURIComponent(html)}`);
+    const image = await window.webContents.capturePage({
+      x: 0,
+      y: 0,
+      width: layout.width,
+      height: layout.height,
+    });
+
+    const outPath = join(app.getPath('temp'), `tuffahi-recap-${Date.now()}.png`);
+    writeFileSync(outPath, image.toPNG());
+    return outPath;
+  } finally {
+    window.destroy();
+  }
+}
@@ -0,0 +1,79 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({ ipcMain: { handle: vi.fn() } }));
+vi.mock('./media-command', () => ({ sendMediaCommand: vi.fn() }));
+vi.mock('./player-state', () => ({ getPlayerState: vi.fn() }));
+vi.mock('./queue', () => ({ getQueue: () => [] }));
+
+import { extractPairingCode, FailedAttemptTracker, generatePairingCode, isAuthorized } from './remote';
+
+describe('isAuthorized', () => {
+  it('accepts the exact code', () => {
+    expect(isAuthorized('012345', '012345')).toBe(true);
+  });
+
+  it('rejects a missing, wrong or differently sized code', () => {
+    expect(isAuthorized(null, '012345')).toBe(false);
+    expect(isAuthorized('', '012345')).toBe(false);
+    expect(isAuthorized('012346', '012345')).toBe(false);
+    expect(isAuthorized('01234', '012345')).toBe(false);
+    expect(isAuthorized('0123456', '012345')).toBe(false);
+  });
+});
+
+describe('extractPairingCode', () => {
+  it('reads a bearer token', () => {
+    expect(extractPairingCode({ authorization: 'Bearer 012345 ' })).toBe('012345');
+  });
+
+  it('reads the pairing code header', () => {
+    expect(extractPairingCode({ 'x-pairing-code': ' 012345' })).toBe('012345');
+  });
+
+  it('ignores other authorization schemes and missing headers', () => {
+    expect(extractPairingCode({ authorization: 'Basic abc' })).toBeNull();
+    expect(extractPairingCode({})).toBeNull();
+  });
+});
+
+describe('generatePairingCode', () => {
+  it('returns six digits', () => {
+    expect(generatePairingCode()).toMatch(/^\d{6}$/);
+  });
+});
+
+describe('FailedAttemptTracker', () => {
+  it('locks an address out after max failures for the lockout period', () => {
+    const tracker = new FailedAttemptTracker(3, 1_000);
+    expect(tracker.recordFailure('a', 0)).toBe(false);
+    expect(tracker.recordFailure('a', 0)).toBe(false);
+    expect(tracker.recordFailure('a', 0)).toBe(true);
+
+    expect(tracker.isLocked('a', 999)).toBe(true);
+    expect(tracker.isLocked('a', 1_000)).toBe(false);
+  });
+
+  it('starts counting again once the lockout ends', () => {
+    const tracker = new FailedAttemptTracker(2, 1_000);
+    tracker.recordFailure('a', 0);
+    tracker.recordFailure('a', 0);
+    tracker.isLocked('a', 1_000);
+
+    expect(tracker.recordFailure('a', 1_000)).toBe(false);
+    expect(tracker.isLocked('a', 1_000)).toBe(false);
+  });
+
+  it('keeps other addresses unlocked', () => {
+    const tracker = new FailedAttemptTracker(1, 1_000);
+    tracker.recordFailure('attacker', 0);
+    expect(tracker.isLocked('attacker', 0)).toBe(true);
+    expect(tracker.isLocked('phone', 0)).toBe(false);
+  });
+
+  it('forgets failures after a success', () => {
+    const tracker = new FailedAttemptTracker(2, 1_000);
+    tracker.recordFailure('a', 0);
+    tracker.recordSuccess('a');
+    expect(tracker.recordFailure('a', 0)).toBe(false);
+  });
+});
@@ -0,0 +1,197 @@
+// LAN remote control: a phone on the same network can play/pause, skip and
+// see what's playing. Opt-in — nothing listens until start-remote-control,
+// and every request must carry the pairing code shown in the app.
+
+import { createServer, IncomingMessage, Server } from 'http';
+import { networkInterfaces } from 'os';
+import { randomInt, timingSafeEqual } from 'crypto';
+import { sendMediaCommand, MediaCommand } from './media-command';
+import { getPlayerState } from './player-state';
+import { getQueue } from './queue';
+import { AppError } from './errors';
+
+export const DEFAULT_REMOTE_PORT = 48620;
+const PAIRING_CODE_DIGITS = 6;
+// Brute-force guard: this many bad codes from one address locks it out for a minute
+const MAX_FAILED_ATTEMPTS = 5;
+const LOCKOUT_MS = 60_000;
+
+export interface RemoteControlInfo {
+  code: string;
+  url: string;
+}
+
+const COMMAND_ROUTES: Record<string, MediaCommand> = {
+  '/play-pause': { type: 'PlayPause' },
+  '/next': { type: 'Next' },
+  '/previous': { type: 'Previous' },
+};
+
+let server: Server | null = null;
+let info: RemoteControlInfo | null = null;
+
+/**
+ * Counts bad pairing codes per client address. Only the address that keeps
+ * guessing gets locked out, so another device on the LAN can't lock out the
+ * paired phone.
+ */
+export class FailedAttemptTracker {
+  private entries = new Map<string, { failures: number; lockedUntil: number }>();
+
+  constructor(
+    private readonly maxFailures: number = MAX_FAILED_ATTEMPTS,
+    private readonly lockoutMs: number = LOCKOUT_MS,
+  ) {}
+
+  isLocked(address: string, now: number): boolean {
+    const entry = this.entries.get(address);
+    if (!entry || entry.lockedUntil === 0) return false;
+    if (now < entry.lockedUntil) return true;
+    this.entries.delete(address);
+    return false;
+  }
+
+  /** Returns true when this failure locks the address out. */
+  recordFailure(address: string, now: number): boolean {
+    const entry = this.entries.get(address) ?? { failures: 0, lockedUntil: 0 };
+    entry.failures++;
+    if (entry.failures >= this.maxFailures) {
+      entry.failures = 0;
+      entry.lockedUntil = now + this.lockoutMs;
+    }
+    this.entries.set(address, entry);
+    return entry.lockedUntil > now;
+  }
+
+  recordSuccess(address: string): void {
+    this.entries.delete(address);
+  }
+}
+
+export function generatePairingCode(): string {
+  return String(randomInt(0, 10 ** PAIRING_CODE_DIGITS)).padStart(PAIRING_CODE_DIGITS, '0');
+}
+
+/**
+ * The code from `Authorization: Bearer <code>` or `X-Pairing-Code`. Never the
+ * query string, which would leak it into proxy logs and browser history.
+ */
+export function extractPairingCode(headers: IncomingMessage['headers']): string | null {
+  const auth = headers.authorization;
+  if (typeof auth === 'string' && auth.startsWith('Bearer ')) {
+    return auth.slice('Bearer '.length).trim();
+  }
+  const header = headers['x-pairing-code'];
+  return typeof header === 'string' ? header.trim() : null;
+}
+
+export function isAuthorized(provided: string | null, expected: string): boolean {
+  if (!provided) return false;
+  const a = Buffer.from(provided);
+  const b = Buffer.from(expected);
+  return a.length === b.length && timingSafeEqual(a, b);
+}
+
+/** First non-internal IPv4 address — the one other devices on the Wi-Fi can reach. */
+export function findLanAddress(): string | null {
+  for (const addresses of Object.values(networkInterfaces())) {
+    for (const address of addresses ?? []) {
+      if (address.family === 'IPv4' && !address.internal) return address.address;
+    }
+  }
+  return null;
+}
+
+function nowPlaying(): Record<string, unknown> {
+  const state = getPlayerState();
+  const item = state.trackId ? getQueue().find((entry) => entry.id === state.trackId) : undefined;
+  return {
+    track_id: state.trackId,
+    title: item?.title ?? null,
+    artist: item?.artist ?? null,
+    position_ms: state.positionMs,
+    duration_ms: state.durationMs,
+    is_playing: state.isPlaying,
+    shuffle: state.shuffle,
+    repeat: state.repeat,
+  };
+}
+
+export async function startRemoteControl(port: number = DEFAULT_REMOTE_PORT): Promise<RemoteControlInfo> {
+  if (server && info) return info;
+  if (!Number.isInteger(port) || port < 1 || port > 65535) {
+    throw new AppError('INVALID_ARGUMENT', `Invalid remote control port: ${port}`, { port });
+  }
+
+  const host = findLanAddress();
+  if (!host) {
+    throw new Error('No local network connection found for remote control');
+  }
+
+  const code = generatePairingCode();
+  const attempts = new FailedAttemptTracker();
+
+  const instance = createServer((req, res) => {
+    const send = (status: number, body: unknown): void => {
+      res.writeHead(status, { 'Content-Type': 'application/json' });
+      res.end(JSON.stringify(body));
+    };
+
+    const address = req.socket.remoteAddress ?? 'unknown';
+    const now = Date.now();
+    if (attempts.isLocked(address, now)) {
+      send(429, { error: 'too many attempts' });
+      return;
+    }
+    if (!isAuthorized(extractPairingCode(req.headers), code)) {
+      if (attempts.recordFailure(address, now)) {
+        console.log(`[TUFFAHI] Remote control locked out ${address} after repeated bad pairing codes`);
+      }
+      send(401, { error: 'invalid pairing code' });
+      return;
+    }
+    attempts.recordSuccess(address);
+
+    const path = (req.url ?? '/').split('?')[0];
+    if (req.method === 'GET' && path === '/now-playing') {
+      send(200, nowPlaying());
+      return;
+    }
+    const command = COMMAND_ROUTES[path];
+    if (req.method === 'POST' && command) {
+      try {
+        sendMediaCommand(command);
+        send(200, { ok: true });
+      } catch (error) {
+        send(503, { error: String(error) });
+      }
+      return;
+    }
+    send(404, { error: 'not found' });
+  });
+
+  await new Promise<void>((resolve, reject) => {
+    instance.once('error', reject);
+    instance.listen(port, host, () => {
+      instance.off('error', reject);
+      resolve();
+    });
+  }).catch((error) => {
+    throw new Error(`Failed to start remote control on ${host}:${port}: ${error}`);
+  });
+
+  server = instance;
+  info = { code, url: `http://${host}:${port}` };
+  console.log(`[TUFFAHI] Remote control listening on ${info.url}`);
+  return info;
+}
+
+export async function stopRemoteControl(): Promise<void> {
+  if (!server) return;
+
+  const instance = server;
+  server = null;
+  info = null;
+  await new Promise<void>((resolve) => instance.close(() => resolve()));
+  console.log('[TUFFAHI] Remote control stopped');
+}
@@ -0,0 +1,46 @@
+// Last.fm scrobble rules: a play counts once half the track, or 4 minutes,
+// has been played — whichever comes first. Tracks under 30s never scrobble.
+// With "full listens only" on, a play must reach 95% of the track instead.
+//
+// There's no Last.fm client in the app yet, so nothing submits scrobbles:
+// the gate is the scrobble-eligibility command. A submission path added
+// later must go through scrobbleEligibility() so the setting applies.
+
+import { getStoreValue, setStoreValue } from './store';
+
+export interface ScrobbleEligibility {
+  eligible: boolean;
+  // Position at which the play becomes eligible; null for tracks too short to scrobble
+  at_ms: number | null;
+}
+
+export const MIN_SCROBBLE_TRACK_MS = 30_000;
+export const MAX_SCROBBLE_THRESHOLD_MS = 4 * 60_000;
+export const FULL_LISTEN_RATIO = 0.95;
+
+export function scrobbleThreshold(trackDurationMs: number, fullOnly: boolean = false): number | null {
+  if (!Number.isFinite(trackDurationMs) || trackDurationMs < MIN_SCROBBLE_TRACK_MS) return null;
+  if (fullOnly) return Math.floor(trackDurationMs * FULL_LISTEN_RATIO);
+  return Math.min(Math.floor(trackDurationMs / 2), MAX_SCROBBLE_THRESHOLD_MS);
+}
+
+export function evaluateScrobble(trackDurationMs: number, positionMs: number, fullOnly: boolean): ScrobbleEligibility {
+  const atMs = scrobbleThreshold(trackDurationMs, fullOnly);
+  return {
+    eligible: atMs !== null && positionMs >= atMs,
+    at_ms: atMs,
+  };
+}
+
+export function getScrobbleFullOnly(): boolean {
+  return getStoreValue<boolean>('scrobbleFullOnly') ?? false;
+}
+
+export function setScrobbleFullOnly(enabled: boolean): boolean {
+  setStoreValue('scrobbleFullOnly', enabled);
+  return enabled;
+}
+
+export function scrobbleEligibility(trackDurationMs: number, positionMs: number): ScrobbleEligibility {
+  return evaluateScrobble(trackDurationMs, positionMs, getScrobbleFullOnly());
+}
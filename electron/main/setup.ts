@@ -0,0 +1,118 @@
+// First-run setup wizard.
+// Shown instead of the main window until the user completes it, so new users
+// learn they need an Apple Music subscription before hitting broken playback.
+
+import { app, BrowserWindow } from 'electron';
+import { join } from 'path';
+import { getStoreValue, setStoreValue } from './store';
+import { registerWindow } from './windows';
+import { getBranding } from './branding';
+
+let setupWindow: BrowserWindow | null = null;
+let onComplete: (() => void) | null = null;
+
+export function isFirstRun(firstRunComplete: unknown, resetRequested: boolean): boolean {
+  return resetRequested || firstRunComplete !== true;
+}
+
+export function needsSetup(): boolean {
+  return isFirstRun(getStoreValue('firstRunComplete'), app.commandLine.hasSwitch('reset-setup'));
+}
+
+function escapeHtml(text: string): string {
+  return text.replace(/[&<>"']/g, (char) => `&#${char.charCodeAt(0)};`);
+}
+
+// The app name comes from branding, so white-label builds greet with their own
+const setupHtml = (appName: string): string => `<!DOCTYPE html>
+<html><head><meta charset="UTF-8"><title>Welcome to ${appName}</title><style>
+*{margin:0;padding:0;box-sizing:border-box}
+body{background:#0a0a0a;color:#fff;font-family:-apple-system,BlinkMacSystemFont,'SF Pro Text','Segoe UI',Roboto,sans-serif;padding:40px;-webkit-user-select:none;user-select:none}
+h1{font-size:24px;margin-bottom:8px}p{color:rgba(255,255,255,.6);font-size:14px;line-height:1.5}
+ol{margin:24px 0;padding-left:20px;color:rgba(255,255,255,.85);font-size:14px;line-height:1.8}
+.s{margin-top:8px;font-size:13px}.ok{color:#14B8A6}.warn{color:#F59E0B}
+ul{margin-top:8px;padding-left:18px;color:rgba(255,255,255,.5);font-size:12px;line-height:1.6}
+button{margin-top:28px;background:#0D9488;color:#fff;border:0;border-radius:8px;padding:10px 24px;font-size:14px;font-weight:600;cursor:pointer}
+button:hover{background:#14B8A6}
+</style></head><body>
+<h1>Welcome to ${appName}</h1>
+<p>A few things to know before you start listening.</p>
+<ol>
+<li>You need an active Apple Music subscription.</li>
+<li>Sign in with your Apple ID from the app when prompted.</li>
+<li>Playback of protected content requires Widevine support on this system.</li>
+</ol>
+<div id="token" class="s"></div>
+<div id="drm" class="s"></div>
+<ul id="notes"></ul>
+<button id="go">Get Started</button>
+<script>
+(async function(){
+  var api = window.electron;
+  try {
+    var configured = await api.isMusicKitConfigured();
+    var t = document.getElementById('token');
+    t.textContent = configured ? 'Developer token: configured' : 'Developer token: missing — this build cannot reach Apple Music';
+    t.className = 's ' + (configured ? 'ok' : 'warn');
+  } catch (e) {}
+  try {
+    var pre = await api.checkPlaybackPrerequisites();
+    var d = document.getElementById('drm');
+    d.textContent = pre.eme_available ? 'Protected playback: available' : 'Protected playback may not work on this system';
+    d.className = 's ' + (pre.eme_available ? 'ok' : 'warn');
+    var ul = document.getElementById('notes');
+    pre.notes.forEach(function(n){ var li = document.createElement('li'); li.textContent = n; ul.appendChild(li); });
+  } catch (e) {}
+  document.getElementById('go').addEventListener('click', function(){ api.completeSetup(); });
+})();
+</script>
+</body></html>`;
+
+export function openSetupWindow(complete: () => void): void {
+  onComplete = complete;
+
+  if (setupWindow && !setupWindow.isDestroyed()) {
+    setupWindow.focus();
+    return;
+  }
+
+  const { appName } = getBranding();
+  setupWindow = new BrowserWindow({
+    width: 520,
+    height: 560,
+    resizable: false,
+    center: true,
+    show: false,
+    title: `Welcome to ${appName}`,
+    backgroundColor: '#0a0a0a',
+    webPreferences: {
+      preload: join(__dirname, '../preload/index.mjs'),
+      contextIsolation: true,
+      nodeIntegration: false,
+      sandbox: false,
+    },
+  });
+
+  registerWindow('setup', setupWindow);
+  setupWindow.loadURL(`data:text/html;charset=utf-8,${encodeURIComponent(setupHtml(escapeHtml(appName)))}`);
+  setupWindow.once('ready-to-show', () => setupWindow?.show());
+
+  setupWindow.on('closed', () => {
+    setupWindow = null;
+    // Closing the wizard without finishing still lets the user into the app;
+    // the flag stays unset so it shows again next launch.
+    const pending = onComplete;
+    onComplete = null;
+    if (!(app as any).isQuitting) pending?.();
+  });
+}
+
+export function completeSetup(): void {
+  setStoreValue('firstRunComplete', true);
+  console.log('[TUFFAHI] First-run setup complete');
+
+  const pending = onComplete;
+  onComplete = null;
+  setupWindow?.close();
+  pending?.();
+}
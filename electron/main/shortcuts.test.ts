@@ -0,0 +1,61 @@
+import { describe, expect, it, vi } from 'vitest';
+
+vi.mock('electron', () => ({
+  globalShortcut: { register: vi.fn(() => true), unregister: vi.fn() },
+}));
+
+vi.mock('./store', () => ({
+  getStoreValue: () => undefined,
+  setStoreValue: vi.fn(),
+}));
+
+vi.mock('./media-keys', () => ({
+  isMediaKey: (accelerator: string) => accelerator.startsWith('Media'),
+  isMediaKeyBound: () => true,
+}));
+
+import { globalShortcut } from 'electron';
+import { setStoreValue } from './store';
+import { findDuplicateBindings, getActiveBindings, setShortcuts } from './shortcuts';
+
+describe('findDuplicateBindings', () => {
+  it('returns nothing when every accelerator is unique', () => {
+    expect(findDuplicateBindings({ a: 'Ctrl+P', b: 'Ctrl+N' })).toEqual([]);
+  });
+
+  it('groups actions that share an accelerator', () => {
+    expect(findDuplicateBindings({ a: 'Ctrl+P', b: 'Ctrl+N', c: 'Ctrl+P' })).toEqual([
+      { accelerator: 'Ctrl+P', actions: ['a', 'c'] },
+    ]);
+  });
+
+  it('compares case-insensitively and resolves modifier aliases', () => {
+    expect(findDuplicateBindings({ a: 'CmdOrCtrl+Shift+M', b: 'commandorcontrol + shift + m' })).toEqual([
+      { accelerator: 'CmdOrCtrl+Shift+M', actions: ['a', 'b'] },
+    ]);
+    expect(findDuplicateBindings({ a: 'Ctrl+Option+K', b: 'Control+Alt+K' })).toHaveLength(1);
+  });
+
+  it('ignores unbound actions', () => {
+    expect(findDuplicateBindings({ a: '', b: '' })).toEqual([]);
+  });
+});
+
+describe('setShortcuts', () => {
+  it('rejects duplicates with DUPLICATE_BINDING and keeps the current bindings', () => {
+    setShortcuts({ 'toggle-miniplayer': 'Ctrl+M' }, () => {});
+    vi.mocked(globalShortcut.register).mockClear();
+    vi.mocked(setStoreValue).mockClear();
+
+    expect(() => setShortcuts({ a: 'Ctrl+P', b: 'ctrl+p' }, () => {})).toThrowError(
+      expect.objectContaining({
+        code: 'DUPLICATE_BINDING',
+        details: { accelerator: 'Ctrl+P', actions: ['a', 'b'] },
+      }),
+    );
+    expect(globalShortcut.register).not.toHaveBeenCalled();
+    expect(globalShortcut.unregister).not.toHaveBeenCalled();
+    expect(setStoreValue).not.toHaveBeenCalled();
+    expect(getActiveBindings()).toEqual({ 'toggle-miniplayer': 'Ctrl+M' });
+  });
+});
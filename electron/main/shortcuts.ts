@@ -0,0 +1,157 @@
+// Global shortcut bindings (action id → accelerator), registered as one set.
+// The whole map is validated before anything is registered, so a bad map
+// leaves the previous bindings in place. Media keys are left to media-keys.ts.
+
+import { globalShortcut } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import { isMediaKey, isMediaKeyBound } from './media-keys';
+import { AppError } from './errors';
+
+// Accelerators compare case-insensitively and Electron treats these as the same modifier
+const MODIFIER_ALIASES: Record<string, string> = {
+  cmdorctrl: 'commandorcontrol',
+  cmd: 'command',
+  ctrl: 'control',
+  option: 'alt',
+};
+
+// Built-in bindings; keep in sync with setupDefaultShortcuts() in the renderer
+export const DEFAULT_SHORTCUTS: Record<string, string> = {
+  'play-pause': 'MediaPlayPause',
+  'next-track': 'MediaTrackNext',
+  'previous-track': 'MediaTrackPrevious',
+  'media-stop': 'MediaStop',
+  'toggle-miniplayer': 'CommandOrControl+Shift+M',
+};
+
+export interface EffectiveShortcut {
+  accelerator: string;
+  is_default: boolean;
+  registered: boolean;
+}
+
+// action id → accelerator currently registered (or handled by the media keys)
+let activeBindings: Record<string, string> = {};
+// What this module registered itself, so clearing leaves the media keys alone
+let ownedAccelerators: string[] = [];
+
+export function normalizeAccelerator(accelerator: string): string {
+  return accelerator
+    .split('+')
+    .map((part) => part.trim().toLowerCase())
+    .map((part) => MODIFIER_ALIASES[part] ?? part)
+    .join('+');
+}
+
+export function findDuplicateBindings(bindings: Record<string, string>): { accelerator: string; actions: string[] }[] {
+  const byAccelerator = new Map<string, { accelerator: string; actions: string[] }>();
+  for (const [action, accelerator] of Object.entries(bindings)) {
+    if (!accelerator) continue;
+    const key = normalizeAccelerator(accelerator);
+    const entry = byAccelerator.get(key);
+    if (entry) {
+      entry.actions.push(action);
+    } else {
+      byAccelerator.set(key, { accelerator, actions: [action] });
+    }
+  }
+  return [...byAccelerator.values()].filter((entry) => entry.actions.length > 1);
+}
+
+/**
+ * Replaces all registered shortcuts with `bindings`. Throws DUPLICATE_BINDING
+ * (details: accelerator, actions) without touching the current registrations
+ * if two actions share an accelerator. Returns the actions whose accelerator the
+ * OS refused (e.g. already taken by another app).
+ */
+export function setShortcuts(
+  bindings: Record<string, string>,
+  onTrigger: (accelerator: string, action: string) => void,
+): { failed: string[] } {
+  const [duplicate] = findDuplicateBindings(bindings);
+  if (duplicate) {
+    throw new AppError(
+      'DUPLICATE_BINDING',
+      `${duplicate.accelerator} is assigned to ${duplicate.actions.join(', ')}`,
+      { accelerator: duplicate.accelerator, actions: duplicate.actions },
+    );
+  }
+
+  clearShortcuts();
+  setStoreValue('shortcutOverrides', diffFromDefaults(bindings, DEFAULT_SHORTCUTS));
+
+  const failed: string[] = [];
+  for (const [action, accelerator] of Object.entries(bindings)) {
+    if (!accelerator) continue;
+    if (isMediaKey(accelerator)) {
+      // Disabled media keys are a user choice, not a failure
+      if (isMediaKeyBound(accelerator)) activeBindings[action] = accelerator;
+      continue;
+    }
+    let ok = false;
+    try {
+      ok = globalShortcut.register(accelerator, () => onTrigger(accelerator, action));
+    } catch {
+      // Invalid accelerator string
+    }
+    if (ok) {
+      activeBindings[action] = accelerator;
+      ownedAccelerators.push(accelerator);
+    } else {
+      failed.push(action);
+    }
+  }
+
+  if (failed.length > 0) {
+    console.log(`[TUFFAHI] Could not register shortcuts for: ${failed.join(', ')}`);
+  }
+  return { failed };
+}
+
+export function clearShortcuts(): void {
+  for (const accelerator of ownedAccelerators) {
+    globalShortcut.unregister(accelerator);
+  }
+  ownedAccelerators = [];
+  activeBindings = {};
+}
+
+export function getActiveBindings(): Record<string, string> {
+  return { ...activeBindings };
+}
+
+/** Bindings that differ from the defaults — what gets persisted. */
+export function diffFromDefaults(
+  bindings: Record<string, string>,
+  defaults: Record<string, string>,
+): Record<string, string> {
+  const overrides: Record<string, string> = {};
+  for (const [action, accelerator] of Object.entries(bindings)) {
+    if (defaults[action] !== accelerator) overrides[action] = accelerator;
+  }
+  return overrides;
+}
+
+/** Stored overrides win over defaults; `registered` reflects what the OS accepted. */
+export function resolveEffectiveShortcuts(
+  defaults: Record<string, string>,
+  overrides: Record<string, string>,
+  active: Record<string, string>,
+): Record<string, EffectiveShortcut> {
+  const effective: Record<string, EffectiveShortcut> = {};
+  for (const action of new Set([...Object.keys(defaults), ...Object.keys(overrides)])) {
+    const isOverride = action in overrides && overrides[action] !== defaults[action];
+    const accelerator = isOverride ? overrides[action] : defaults[action];
+    effective[action] = {
+      accelerator,
+      is_default: !isOverride,
+      registered: !!accelerator && active[action] === accelerator,
+    };
+  }
+  return effective;
+}
+
+export function getEffectiveShortcuts(): Record<string, EffectiveShortcut> {
+  const overrides = getStoreValue<Record<string, string>>('shortcutOverrides') ?? {};
+  return resolveEffectiveShortcuts(DEFAULT_SHORTCUTS, overrides, activeBindings);
+}
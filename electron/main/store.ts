@@ -0,0 +1,50 @@
+// Persistent key/value store for main-process settings.
+// Backed by a JSON file in the userData directory; read once, written on change.
+
+import { app } from 'electron';
+import { join } from 'path';
+import { existsSync, readFileSync, writeFileSync, mkdirSync } from 'fs';
+
+let cache: Record<string, unknown> | null = null;
+
+function storePath(): string {
+  return join(app.getPath('userData'), 'settings.json');
+}
+
+function load(): Record<string, unknown> {
+  if (cache) return cache;
+
+  try {
+    const path = storePath();
+    cache = existsSync(path) ? JSON.parse(readFileSync(path, 'utf-8')) : {};
+  } catch (error) {
+    console.warn('[TUFFAHI] Failed to read settings store, starting empty:', error);
+    cache = {};
+  }
+  return cache!;
+}
+
+function persist(): void {
+  try {
+    mkdirSync(app.getPath('userData'), { recursive: true });
+    writeFileSync(storePath(), JSON.stringify(cache ?? {}, null, 2));
+  } catch (error) {
+    console.error('[TUFFAHI] Failed to write settings store:', error);
+  }
+}
+
+export function getStoreValue<T>(key: string): T | undefined {
+  return load()[key] as T | undefined;
+}
+
+export function setStoreValue(key: string, value: unknown): void {
+  load()[key] = value;
+  persist();
+}
+
+export function deleteStoreValue(key: string): void {
+  const data = load();
+  if (!(key in data)) return;
+  delete data[key];
+  persist();
+}
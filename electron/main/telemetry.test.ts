@@ -0,0 +1,59 @@
+import { describe, expect, it, vi } from 'vitest';
+
+const { store } = vi.hoisted(() => ({ store: new Map<string, unknown>([['telemetryEnabled', true]]) }));
+
+vi.mock('electron', () => ({ app: { getVersion: () => '1.0.0' }, net: { fetch: vi.fn() } }));
+vi.mock('./store', () => ({
+  getStoreValue: (key: string) => store.get(key),
+  setStoreValue: (key: string, value: unknown) => store.set(key, value),
+}));
+
+import { buildPayload, getTelemetryStatus, isAllowedEventName, recordTelemetryEvent, requeueBatch } from './telemetry';
+
+describe('isAllowedEventName', () => {
+  it('accepts short lowercase feature names', () => {
+    expect(isAllowedEventName('mini_player.open')).toBe(true);
+    expect(isAllowedEventName('lyrics-view')).toBe(true);
+  });
+
+  it('rejects anything that could carry free text', () => {
+    expect(isAllowedEventName('Played: Song Title')).toBe(false);
+    expect(isAllowedEventName('user@example.com')).toBe(false);
+    expect(isAllowedEventName('a'.repeat(65))).toBe(false);
+    expect(isAllowedEventName('')).toBe(false);
+    expect(isAllowedEventName(42)).toBe(false);
+  });
+});
+
+describe('buildPayload', () => {
+  it('contains only the version, platform and event counts', () => {
+    const payload = buildPayload(new Map([['search', 3]]), '1.2.3', 'darwin');
+    expect(payload).toEqual({ app_version: '1.2.3', platform: 'darwin', events: [{ event: 'search', count: 3 }] });
+  });
+});
+
+describe('recordTelemetryEvent', () => {
+  it('only queues allowlisted names with positive integer counts', () => {
+    recordTelemetryEvent('Now playing: Song');
+    recordTelemetryEvent('search', 0);
+    recordTelemetryEvent('search', 1.5);
+    expect(getTelemetryStatus().pending).toBe(0);
+
+    recordTelemetryEvent('search');
+    expect(getTelemetryStatus().pending).toBe(1);
+  });
+});
+
+describe('requeueBatch', () => {
+  it('merges counts for names already pending', () => {
+    const pending = new Map([['search', 2]]);
+    requeueBatch(pending, new Map([['search', 3], ['play', 1]]));
+    expect(pending).toEqual(new Map([['search', 5], ['play', 1]]));
+  });
+
+  it('drops new names past the cap but keeps counting existing ones', () => {
+    const pending = new Map([['search', 1]]);
+    requeueBatch(pending, new Map([['search', 1], ['play', 1], ['skip', 1]]), 2);
+    expect(pending).toEqual(new Map([['search', 2], ['play', 1]]));
+  });
+});
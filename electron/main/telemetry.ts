@@ -0,0 +1,162 @@
+// Opt-in anonymous usage telemetry.
+// Off by default. Only feature counters are sent — event names from a strict
+// pattern plus a count; never track titles, user tokens, or other PII.
+
+import { app, net } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+
+export interface TelemetryEvent {
+  event: string;
+  count: number;
+}
+
+export interface TelemetryPayload {
+  app_version: string;
+  platform: NodeJS.Platform;
+  events: TelemetryEvent[];
+}
+
+export interface TelemetryStatus {
+  enabled: boolean;
+  endpoint: string | null;
+  pending: number;
+}
+
+const EVENT_NAME = /^[a-z0-9_.-]{1,64}$/;
+const FLUSH_INTERVAL_MS = 60_000;
+const FLUSH_THRESHOLD = 20;
+const MAX_ATTEMPTS = 3;
+const RETRY_BASE_MS = 2_000;
+// Distinct event names kept across failed flushes; counts for names already
+// kept still accumulate past this
+const MAX_PENDING_EVENTS = 200;
+
+// event name → count, aggregated between flushes
+const pending = new Map<string, number>();
+let flushTimer: ReturnType<typeof setInterval> | null = null;
+
+export function isTelemetryEnabled(): boolean {
+  return getStoreValue<boolean>('telemetryEnabled') === true;
+}
+
+function getEndpoint(): string | null {
+  return getStoreValue<string>('telemetryEndpoint') ?? null;
+}
+
+export function isAllowedEventName(name: unknown): name is string {
+  return typeof name === 'string' && EVENT_NAME.test(name);
+}
+
+/** Builds the wire payload with only allowlisted fields. */
+export function buildPayload(events: Map<string, number>, version: string, platform: NodeJS.Platform): TelemetryPayload {
+  return {
+    app_version: version,
+    platform,
+    events: [...events.entries()].map(([event, count]) => ({ event, count })),
+  };
+}
+
+/** Adds a failed batch back into pending, dropping new names once the cap is hit. */
+export function requeueBatch(into: Map<string, number>, batch: Map<string, number>, max: number = MAX_PENDING_EVENTS): void {
+  for (const [event, count] of batch) {
+    const existing = into.get(event);
+    if (existing !== undefined) {
+      into.set(event, existing + count);
+    } else if (into.size < max) {
+      into.set(event, count);
+    }
+  }
+}
+
+export function recordTelemetryEvent(name: string, count: number = 1): void {
+  if (!isTelemetryEnabled()) return;
+  if (!isAllowedEventName(name) || !Number.isInteger(count) || count < 1) return;
+
+  pending.set(name, (pending.get(name) ?? 0) + count);
+  if (pending.size >= FLUSH_THRESHOLD) {
+    flushTelemetry().catch(() => {});
+  }
+}
+
+export async function flushTelemetry(
+  send: (endpoint: string, body: string) => Promise<boolean> = postPayload,
+): Promise<void> {
+  const endpoint = getEndpoint();
+  if (!isTelemetryEnabled() || !endpoint || pending.size === 0) return;
+
+  const batch = new Map(pending);
+  pending.clear();
+  const body = JSON.stringify(buildPayload(batch, app.getVersion(), process.platform));
+
+  for (let attempt = 0; attempt < MAX_ATTEMPTS; attempt++) {
+    // Re-check each attempt — the user may have opted out mid-retry
+    if (!isTelemetryEnabled()) return;
+    try {
+      if (await send(endpoint, body)) return;
+    } catch {
+      // Network error — retry below
+    }
+    await new Promise((resolve) => setTimeout(resolve, RETRY_BASE_MS * Math.pow(2, attempt)));
+  }
+  // Still failing — keep the counts for the next flush unless the user opted out
+  if (isTelemetryEnabled()) requeueBatch(pending, batch);
+}
+
+async function postPayload(endpoint: string, body: string): Promise<boolean> {
+  const response = await net.fetch(endpoint, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body,
+  });
+  return response.ok;
+}
+
+function startFlushTimer(): void {
+  if (flushTimer) return;
+  flushTimer = setInterval(() => {
+    flushTelemetry().catch(() => {});
+  }, FLUSH_INTERVAL_MS);
+}
+
+function stopFlushTimer(): void {
+  if (flushTimer) {
+    clearInterval(flushTimer);
+    flushTimer = null;
+  }
+}
+
+export function initTelemetry(): void {
+  if (isTelemetryEnabled()) startFlushTimer();
+}
+
+export function setTelemetry(enabled: boolean, endpoint?: string): TelemetryStatus {
+  if (endpoint !== undefined) {
+    let parsed: URL;
+    try {
+      parsed = new URL(endpoint);
+    } catch {
+      throw new Error(`Invalid telemetry endpoint: ${endpoint}`);
+    }
+    if (parsed.protocol !== 'https:') {
+      throw new Error('Telemetry endpoint must use https');
+    }
+    setStoreValue('telemetryEndpoint', endpoint);
+  }
+
+  setStoreValue('telemetryEnabled', enabled);
+  if (enabled) {
+    startFlushTimer();
+  } else {
+    stopFlushTimer();
+    pending.clear();
+  }
+  return getTelemetryStatus();
+}
+
+export function getTelemetryStatus(): TelemetryStatus {
+  return {
+    enabled: isTelemetryEnabled(),
+    endpoint: getEndpoint(),
+    pending: pending.size,
+  };
+}
@@ -1,21 +1,458 @@
 // The developer token is generated at build time from .env credentials.
 // Only the signed JWT is embedded — the private key never ships in the binary.
+//
+// A key can also be rotated in at runtime (rotate-private-key); from then on
+// tokens are signed in the main process with that key, including after a
+// restart. Builds without .env credentials fall back to ones saved with
+// set-musickit-config.
+//
+// Runtime signing uses the callback form of crypto.sign/verify, which runs on
+// libuv's threadpool, and the result is cached until near expiry, so no ECDSA
+// work happens synchronously on the main process's event loop.
+import { safeStorage } from 'electron';
+import { createHash, createPrivateKey, createPublicKey, sign, verify, KeyObject } from 'crypto';
+import { promisify } from 'util';
+import { emitToAll } from './events';
+import { AppError } from './errors';
+import { getStoreValue, setStoreValue } from './store';
+
 declare const __APPLE_DEVELOPER_TOKEN__: string;
+// SHA-256 (truncated) of the signing key's DER public key, computed at build time
+declare const __APPLE_KEY_FINGERPRINT__: string;
 
 const DEVELOPER_TOKEN = __APPLE_DEVELOPER_TOKEN__;
+const KEY_FINGERPRINT = __APPLE_KEY_FINGERPRINT__;
+
+const TOKEN_TTL_SECONDS = 180 * 24 * 60 * 60; // 180 days, same as the build-time token
+// Re-sign this long before exp so callers never get a token about to lapse
+export const TOKEN_REFRESH_MARGIN_SECONDS = 24 * 60 * 60;
+const FINGERPRINT_BYTES = 16;
+
+export interface SigningConfig {
+  teamId: string;
+  keyId: string;
+  privateKey: KeyObject;
+}
+
+// Runtime signing state — swapped together so readers never see a mix
+let active: { config: SigningConfig; token: string; exp: number } | null = null;
+// Re-sign in progress, shared so concurrent callers don't sign twice
+let resigning: Promise<string> | null = null;
+// exp of the build-time token, decoded on first use (null if it has none)
+let buildTokenExp: number | null | undefined;
+let expiryReported = false;
+
+const signAsync = promisify(sign);
+const verifyAsync = promisify(verify);
+
+function nowSeconds(): number {
+  return Math.floor(Date.now() / 1000);
+}
+
+function isBuildTokenExpired(now: number = nowSeconds()): boolean {
+  if (!DEVELOPER_TOKEN) return false;
+  if (buildTokenExp === undefined) {
+    const { exp } = decodeTokenClaims(DEVELOPER_TOKEN);
+    buildTokenExp = typeof exp === 'number' ? exp : null;
+  }
+  return buildTokenExp !== null && buildTokenExp <= now;
+}
+
+export function needsRefresh(exp: number, now: number, margin: number = TOKEN_REFRESH_MARGIN_SECONDS): boolean {
+  return exp - now <= margin;
+}
+
+async function signActive(config: SigningConfig): Promise<NonNullable<typeof active>> {
+  const now = nowSeconds();
+  return { config, token: await signDeveloperToken(config, now), exp: now + TOKEN_TTL_SECONDS };
+}
+
+function resignActive(): Promise<string> {
+  if (!resigning) {
+    const config = active!.config;
+    resigning = signActive(config)
+      .then((next) => {
+        // A rotation that landed meanwhile wins
+        if (active?.config === config) active = next;
+        return active!.token;
+      })
+      .finally(() => {
+        resigning = null;
+      });
+  }
+  return resigning;
+}
+
+// ── Stored credentials ──────────────────────────────────────────────────────
+
+interface StoredCredentials {
+  teamId: string;
+  keyId: string;
+  // base64 of safeStorage ciphertext when encrypted, else the PEM itself
+  privateKey: string;
+  encrypted: boolean;
+  // Rotated keys replace the build-time token on later launches too
+  rotated?: boolean;
+}
+
+const APPLE_ID_PATTERN = /^[A-Z0-9]{10}$/;
+
+let storedConfigLoad: Promise<void> | null = null;
+
+function hasStoredCredentials(): boolean {
+  return !!getStoreValue<StoredCredentials>('musicKitCredentials');
+}
 
+/**
+ * Saves the key encrypted with safeStorage. Without OS encryption the PEM
+ * would land in settings.json as-is, so nothing is saved (returns false)
+ * unless the caller explicitly allowed plaintext.
+ */
+function saveCredentials(
+  teamId: string,
+  keyId: string,
+  keyContent: string,
+  rotated: boolean,
+  allowPlaintext: boolean,
+): boolean {
+  const pem = keyContent.trim();
+  const encrypted = safeStorage.isEncryptionAvailable();
+  if (!encrypted && !allowPlaintext) return false;
+  if (!encrypted) {
+    console.warn(`[TUFFAHI] OS encryption unavailable; saving key ${keyId} unencrypted as requested`);
+  }
+  const stored: StoredCredentials = {
+    teamId,
+    keyId,
+    privateKey: encrypted ? safeStorage.encryptString(pem).toString('base64') : pem,
+    encrypted,
+    rotated,
+  };
+  setStoreValue('musicKitCredentials', stored);
+  return true;
+}
+
+/** The saved signing config, or null if none is saved or it no longer loads. */
+export function loadSigningConfigFromStore(): SigningConfig | null {
+  const stored = getStoreValue<StoredCredentials>('musicKitCredentials');
+  if (!stored) return null;
+  try {
+    const pem = stored.encrypted
+      ? safeStorage.decryptString(Buffer.from(stored.privateKey, 'base64'))
+      : stored.privateKey;
+    return { teamId: stored.teamId, keyId: stored.keyId, privateKey: parsePrivateKey(pem) };
+  } catch {
+    console.warn('[TUFFAHI] Saved MusicKit credentials could not be loaded');
+    return null;
+  }
+}
+
+// Sign with the saved credentials (once per run) when there's no usable
+// build-time token, or when the saved key was rotated in over it
+function useStoredConfigIfNeeded(): Promise<void> {
+  if (active) return Promise.resolve();
+  if (
+    DEVELOPER_TOKEN &&
+    !isBuildTokenExpired() &&
+    !getStoreValue<StoredCredentials>('musicKitCredentials')?.rotated
+  ) {
+    return Promise.resolve();
+  }
+  if (!storedConfigLoad) {
+    storedConfigLoad = (async () => {
+      const config = loadSigningConfigFromStore();
+      if (config && !active) active = await signActive(config);
+    })();
+  }
+  return storedConfigLoad;
+}
+
+/**
+ * Validates and saves MusicKit credentials. The key must parse and sign a
+ * token that verifies; otherwise nothing is saved. Without OS encryption it
+ * fails with KEY_STORAGE_UNAVAILABLE unless allowPlaintext is set. Takes
+ * effect right away unless the build has its own token, which always wins.
+ */
+export async function setMusicKitConfig(
+  teamId: string,
+  keyId: string,
+  keyContent: string,
+  allowPlaintext: boolean = false,
+): Promise<void> {
+  if (!APPLE_ID_PATTERN.test(teamId)) {
+    throw new AppError('INVALID_ARGUMENT', 'Invalid team ID: expected 10 uppercase alphanumeric characters', {
+      team_id: teamId,
+    });
+  }
+  if (!APPLE_ID_PATTERN.test(keyId)) {
+    throw new AppError('INVALID_ARGUMENT', 'Invalid key ID: expected 10 uppercase alphanumeric characters', {
+      key_id: keyId,
+    });
+  }
+
+  const privateKey = parsePrivateKey(keyContent);
+  const candidate = await signActive({ teamId, keyId, privateKey });
+  if (!(await verifyDeveloperToken(candidate.token, createPublicKey(privateKey)))) {
+    throw new AppError('KEY_VERIFICATION_FAILED', 'Generated token failed verification; credentials not saved', {
+      key_id: keyId,
+    });
+  }
+
+  if (!saveCredentials(teamId, keyId, keyContent, false, allowPlaintext)) {
+    throw new AppError(
+      'KEY_STORAGE_UNAVAILABLE',
+      'OS encryption is unavailable, so the private key would be saved unencrypted; credentials not saved',
+      { key_id: keyId },
+    );
+  }
+  console.log(`[TUFFAHI] Saved MusicKit credentials for key ${keyId}`);
+
+  if (DEVELOPER_TOKEN) return;
+  storedConfigLoad = Promise.resolve();
+  active = candidate;
+  emitToAll('developer-token-refreshed', candidate.token);
+}
+
+// Doesn't sign anything: saved credentials count once they're stored
 export function isMusicKitConfigured(): boolean {
-  return !!DEVELOPER_TOKEN;
+  return !!active || !!DEVELOPER_TOKEN || hasStoredCredentials();
 }
 
-export function getDeveloperToken(): string {
+export async function getDeveloperToken(): Promise<string> {
+  await useStoredConfigIfNeeded();
+  if (active) {
+    if (needsRefresh(active.exp, nowSeconds())) {
+      const token = await resignActive();
+      console.log('[TUFFAHI] Developer token near expiry, re-signed');
+      emitToAll('developer-token-refreshed', token);
+      return token;
+    }
+    return active.token;
+  }
   if (!DEVELOPER_TOKEN) {
-    throw new Error('Developer token not configured — rebuild with Apple credentials in .env');
+    throw new AppError(
+      'TOKEN_NOT_CONFIGURED',
+      'Developer token not configured — save credentials in setup or rebuild with Apple credentials in .env',
+    );
+  }
+  // The build-time token can't be re-signed; without saved credentials to
+  // fall back on, say so instead of letting MusicKit fail on it
+  if (isBuildTokenExpired()) {
+    if (!expiryReported) {
+      expiryReported = true;
+      console.warn('[TUFFAHI] Built-in developer token has expired');
+      emitToAll('developer-token-expired', { exp: buildTokenExp });
+    }
+    throw new AppError(
+      'TOKEN_INVALID',
+      'The built-in developer token has expired — save MusicKit credentials in settings or rebuild the app',
+      { exp: buildTokenExp },
+    );
   }
   return DEVELOPER_TOKEN;
 }
 
-export function refreshDeveloperToken(): string {
+export async function refreshDeveloperToken(): Promise<string> {
+  await useStoredConfigIfNeeded();
+  if (active) return resignActive();
   // Token is baked in at build time — rebuild the app to get a new one
   return getDeveloperToken();
 }
+
+/** When the token getDeveloperToken() would return expires. exp is null if the token has none. */
+export async function getDeveloperTokenExpiry(): Promise<{ exp: number | null; remaining_seconds: number | null }> {
+  await useStoredConfigIfNeeded();
+  // Read the build-time exp directly so an expired token still reports it
+  const exp = active ? active.exp : decodeTokenClaims(DEVELOPER_TOKEN || (await getDeveloperToken())).exp;
+  if (typeof exp !== 'number') return { exp: null, remaining_seconds: null };
+  return { exp, remaining_seconds: exp - nowSeconds() };
+}
+
+/** Whether that token exists and its exp hasn't passed. A token without exp doesn't count. */
+export async function isDeveloperTokenValid(): Promise<boolean> {
+  try {
+    const { remaining_seconds } = await getDeveloperTokenExpiry();
+    return remaining_seconds !== null && remaining_seconds > 0;
+  } catch {
+    return false;
+  }
+}
+
+export function decodeTokenHeader(token: string): Record<string, unknown> {
+  return decodeTokenPart(token, 0);
+}
+
+export function decodeTokenClaims(token: string): Record<string, unknown> {
+  return decodeTokenPart(token, 1);
+}
+
+function decodeTokenPart(token: string, index: number): Record<string, unknown> {
+  const part = token.split('.')[index];
+  try {
+    return JSON.parse(Buffer.from(part, 'base64url').toString('utf-8'));
+  } catch {
+    throw new AppError('TOKEN_INVALID', 'Developer token is not a valid JWT');
+  }
+}
+
+export function parsePrivateKey(content: string): KeyObject {
+  let key: KeyObject;
+  try {
+    key = createPrivateKey(content.trim());
+  } catch {
+    throw new AppError('KEY_INVALID', 'Invalid private key: could not parse PEM/PKCS#8 content');
+  }
+  if (key.asymmetricKeyType !== 'ec' || key.asymmetricKeyDetails?.namedCurve !== 'prime256v1') {
+    throw new AppError('KEY_INVALID', 'Invalid private key: MusicKit keys must be P-256 EC keys', {
+      key_type: key.asymmetricKeyType,
+    });
+  }
+  return key;
+}
+
+export async function signDeveloperToken(config: SigningConfig, now: number = nowSeconds()): Promise<string> {
+  const header = { alg: 'ES256', kid: config.keyId };
+  const claims = { iss: config.teamId, iat: now, exp: now + TOKEN_TTL_SECONDS };
+  const signingInput = [header, claims]
+    .map((part) => Buffer.from(JSON.stringify(part)).toString('base64url'))
+    .join('.');
+  // JWS wants the raw r||s signature, not DER
+  const signature = await signAsync('sha256', Buffer.from(signingInput), {
+    key: config.privateKey,
+    dsaEncoding: 'ieee-p1363',
+  });
+  return `${signingInput}.${signature.toString('base64url')}`;
+}
+
+export async function verifyDeveloperToken(token: string, publicKey: KeyObject): Promise<boolean> {
+  const [header, claims, signature] = token.split('.');
+  if (!header || !claims || !signature) return false;
+  return verifyAsync(
+    'sha256',
+    Buffer.from(`${header}.${claims}`),
+    { key: publicKey, dsaEncoding: 'ieee-p1363' },
+    Buffer.from(signature, 'base64url'),
+  );
+}
+
+async function currentTeamId(): Promise<string | null> {
+  await useStoredConfigIfNeeded();
+  const token = active?.token ?? DEVELOPER_TOKEN;
+  if (!token) return null;
+  const { iss } = decodeTokenClaims(token);
+  return typeof iss === 'string' ? iss : null;
+}
+
+/**
+ * Swaps in a new signing key without a restart and saves it, so it stays in
+ * use after relaunching. The new key must parse and produce a token that
+ * verifies against its own public key; otherwise the old key stays active
+ * and an error is returned. Without OS encryption the key isn't written to
+ * disk and the rotation lasts for this session only (persisted: false).
+ */
+export async function rotatePrivateKey(keyContent: string, keyId: string): Promise<{ persisted: boolean }> {
+  if (!APPLE_ID_PATTERN.test(keyId)) {
+    throw new AppError('INVALID_ARGUMENT', 'Invalid key ID: expected 10 uppercase alphanumeric characters', {
+      key_id: keyId,
+    });
+  }
+  const teamId = await currentTeamId();
+  if (!teamId) {
+    throw new AppError('TOKEN_NOT_CONFIGURED', 'Cannot rotate key: no team ID configured');
+  }
+
+  const privateKey = parsePrivateKey(keyContent);
+  const candidate = await signActive({ teamId, keyId, privateKey });
+  if (!(await verifyDeveloperToken(candidate.token, createPublicKey(privateKey)))) {
+    throw new AppError('KEY_VERIFICATION_FAILED', 'Key rotation aborted: generated token failed verification', {
+      key_id: keyId,
+    });
+  }
+
+  const persisted = saveCredentials(teamId, keyId, keyContent, true, false);
+  if (!persisted) {
+    console.warn('[TUFFAHI] OS encryption unavailable; rotated key kept for this session only');
+  }
+  active = candidate;
+  console.log(`[TUFFAHI] Rotated signing key to ${keyId}`);
+  emitToAll('developer-token-refreshed', candidate.token);
+  return { persisted };
+}
+
+export function fingerprintKey(key: KeyObject): string {
+  const der = createPublicKey(key).export({ type: 'spki', format: 'der' });
+  return createHash('sha256').update(der).digest('hex').slice(0, FINGERPRINT_BYTES * 2);
+}
+
+export async function getSigningKeyFingerprint(): Promise<{ fingerprint: string; key_id: string }> {
+  await useStoredConfigIfNeeded();
+  if (active) {
+    return { fingerprint: fingerprintKey(active.config.privateKey), key_id: active.config.keyId };
+  }
+  if (!DEVELOPER_TOKEN || !KEY_FINGERPRINT) {
+    throw new AppError('TOKEN_NOT_CONFIGURED', 'Signing key not configured — rebuild with Apple credentials in .env');
+  }
+  const { kid } = decodeTokenHeader(DEVELOPER_TOKEN);
+  return { fingerprint: KEY_FINGERPRINT, key_id: String(kid ?? '') };
+}
+
+// ── Auth debug trace ────────────────────────────────────────────────────────
+
+export type TokenSource = 'runtime-key' | 'build-time';
+
+export interface AuthDebugTrace {
+  source: TokenSource;
+  cached: boolean;
+  header: Record<string, unknown>;
+  claims: Record<string, unknown>;
+  ttl_seconds: number | null;
+}
+
+// Only these fields are copied out, so nothing else a token might carry leaks
+const TRACE_HEADER_FIELDS = ['alg', 'kid', 'typ'];
+const TRACE_CLAIM_FIELDS = ['iss', 'iat', 'exp', 'origin'];
+
+function pick(source: Record<string, unknown>, fields: string[]): Record<string, unknown> {
+  const picked: Record<string, unknown> = {};
+  for (const field of fields) {
+    if (field in source) picked[field] = source[field];
+  }
+  return picked;
+}
+
+/** Decoded header/claims plus where the token came from. Never the signature or key. */
+export function buildAuthDebugTrace(
+  token: string,
+  source: TokenSource,
+  cached: boolean,
+  now: number = nowSeconds(),
+): AuthDebugTrace {
+  const claims = pick(decodeTokenClaims(token), TRACE_CLAIM_FIELDS);
+  return {
+    source,
+    cached,
+    header: pick(decodeTokenHeader(token), TRACE_HEADER_FIELDS),
+    claims,
+    ttl_seconds: typeof claims.exp === 'number' ? claims.exp - now : null,
+  };
+}
+
+export function getAuthDebugEnabled(): boolean {
+  return getStoreValue<boolean>('authDebugTrace') ?? false;
+}
+
+export function setAuthDebugEnabled(enabled: boolean): boolean {
+  setStoreValue('authDebugTrace', enabled);
+  return enabled;
+}
+
+export async function getAuthDebugTrace(): Promise<AuthDebugTrace> {
+  if (!getAuthDebugEnabled()) {
+    throw new AppError('DEBUG_DISABLED', 'Auth debug trace is off — enable it in settings first');
+  }
+  await useStoredConfigIfNeeded();
+  // Runtime tokens are signed once and reused; the build-time one is embedded
+  return active
+    ? buildAuthDebugTrace(active.token, 'runtime-key', true)
+    : buildAuthDebugTrace(DEVELOPER_TOKEN || (await getDeveloperToken()), 'build-time', false);
+}
@@ -0,0 +1,51 @@
+// Keeps the main window hidden until the renderer says it's ready
+// (MusicKit configured, UI mounted), so slow machines don't flash an empty
+// window. A timeout shows it anyway in case the renderer never reports in.
+
+export const FRONTEND_READY_TIMEOUT_MS = 8000;
+
+export type ShowReason = 'ready' | 'timeout';
+
+export class ShowOnReady {
+  private shown = false;
+  private timer: ReturnType<typeof setTimeout> | null = null;
+
+  constructor(
+    private readonly show: (reason: ShowReason) => void,
+    timeoutMs: number = FRONTEND_READY_TIMEOUT_MS,
+  ) {
+    this.timer = setTimeout(() => this.fire('timeout'), timeoutMs);
+  }
+
+  get isShown(): boolean {
+    return this.shown;
+  }
+
+  ready(): void {
+    this.fire('ready');
+  }
+
+  cancel(): void {
+    if (this.timer) clearTimeout(this.timer);
+    this.timer = null;
+  }
+
+  private fire(reason: ShowReason): void {
+    if (this.shown) return;
+    this.shown = true;
+    this.cancel();
+    this.show(reason);
+  }
+}
+
+let gate: ShowOnReady | null = null;
+
+export function setMainWindowGate(next: ShowOnReady | null): void {
+  gate?.cancel();
+  gate = next;
+}
+
+/** Called by the renderer once initialized; later calls (e.g. reloads) are no-ops. */
+export function frontendReady(): void {
+  gate?.ready();
+}
@@ -0,0 +1,113 @@
+// Window registry keyed by label ("main", "setup", "auth"), plus per-window
+// minimum size constraints and macOS traffic light insets persisted across sessions.
+
+import type { BrowserWindow } from 'electron';
+import { getStoreValue, setStoreValue } from './store';
+import { AppError, windowNotFound } from './errors';
+
+export interface MinSize {
+  width: number;
+  height: number;
+}
+
+// Below this no layout in the app is usable
+export const MIN_SIZE_FLOOR: MinSize = { width: 200, height: 200 };
+
+export const DEFAULT_MIN_SIZES: Record<string, MinSize> = {
+  main: { width: 800, height: 600 },
+};
+
+const windows = new Map<string, BrowserWindow>();
+
+export function registerWindow(label: string, window: BrowserWindow): void {
+  windows.set(label, window);
+  applyTrafficLightInset(window, getTrafficLightInset(label));
+  window.on('closed', () => {
+    if (windows.get(label) === window) windows.delete(label);
+  });
+}
+
+export function getWindowByLabel(label: string): BrowserWindow | null {
+  const window = windows.get(label);
+  return window && !window.isDestroyed() ? window : null;
+}
+
+export function requireWindow(label: string): BrowserWindow {
+  const window = getWindowByLabel(label);
+  if (!window) throw windowNotFound(label);
+  return window;
+}
+
+export function validateMinSize(width: number, height: number): MinSize {
+  if (!Number.isInteger(width) || !Number.isInteger(height)) {
+    throw new AppError('INVALID_ARGUMENT', 'Minimum size must be whole pixels', { width, height });
+  }
+  if (width < MIN_SIZE_FLOOR.width || height < MIN_SIZE_FLOOR.height) {
+    throw new AppError(
+      'INVALID_ARGUMENT',
+      `Minimum size cannot be below ${MIN_SIZE_FLOOR.width}x${MIN_SIZE_FLOOR.height}`,
+      { width, height },
+    );
+  }
+  return { width, height };
+}
+
+export function getWindowMinSize(label: string): MinSize | null {
+  const stored = getStoreValue<Record<string, MinSize>>('windowMinSizes')?.[label];
+  return stored ?? DEFAULT_MIN_SIZES[label] ?? null;
+}
+
+export function setWindowMinSize(label: string, width: number, height: number, apply: boolean = true): MinSize {
+  const size = validateMinSize(width, height);
+  const all = getStoreValue<Record<string, MinSize>>('windowMinSizes') ?? {};
+  setStoreValue('windowMinSizes', { ...all, [label]: size });
+
+  if (apply) {
+    getWindowByLabel(label)?.setMinimumSize(size.width, size.height);
+  }
+  return size;
+}
+
+// ── Traffic lights (macOS) ──────────────────────────────────────────────────
+
+export interface TrafficLightInset {
+  x: number;
+  y: number;
+}
+
+// Beyond this the buttons would sit in the middle of the content
+export const MAX_TRAFFIC_LIGHT_INSET = 200;
+
+export function validateTrafficLightInset(x: number, y: number): TrafficLightInset {
+  if (!Number.isInteger(x) || !Number.isInteger(y)) {
+    throw new AppError('INVALID_ARGUMENT', 'Traffic light inset must be whole pixels', { x, y });
+  }
+  if (x < 0 || y < 0 || x > MAX_TRAFFIC_LIGHT_INSET || y > MAX_TRAFFIC_LIGHT_INSET) {
+    throw new AppError('INVALID_ARGUMENT', `Traffic light inset must be within 0-${MAX_TRAFFIC_LIGHT_INSET}px`, {
+      x,
+      y,
+    });
+  }
+  return { x, y };
+}
+
+export function getTrafficLightInset(label: string): TrafficLightInset | null {
+  return getStoreValue<Record<string, TrafficLightInset>>('trafficLightInsets')?.[label] ?? null;
+}
+
+// Electron keeps the position across resizes, so applying once per window is enough
+function applyTrafficLightInset(window: BrowserWindow, inset: TrafficLightInset | null): void {
+  if (process.platform !== 'darwin' || !inset) return;
+  window.setWindowButtonPosition(inset);
+}
+
+/** Persists the inset for `label` and applies it now if that window is open. No-op off macOS. */
+export function setTrafficLightInset(label: string, x: number, y: number): TrafficLightInset {
+  const inset = validateTrafficLightInset(x, y);
+  const all = getStoreValue<Record<string, TrafficLightInset>>('trafficLightInsets') ?? {};
+  setStoreValue('trafficLightInsets', { ...all, [label]: inset });
+
+  const window = getWindowByLabel(label);
+  if (window) applyTrafficLightInset(window, inset);
+  return inset;
+}
@@ -1,28 +1,87 @@
 import { contextBridge, ipcRenderer } from 'electron';
 
+// Commands registered with handleCommand() reject with "AppError:{json}".
+// Unwrap it into a plain { code, message, details } object — custom Error
+// properties don't survive the context bridge.
+const APP_ERROR_PREFIX = 'AppError:';
+
+function invokeCommand(channel: string, ...args: unknown[]): Promise<any> {
+  return ipcRenderer.invoke(channel, ...args).catch((error: Error) => {
+    const index = error.message.indexOf(APP_ERROR_PREFIX);
+    if (index === -1) throw error;
+    throw JSON.parse(error.message.slice(index + APP_ERROR_PREFIX.length));
+  });
+}
+
 contextBridge.exposeInMainWorld('electron', {
   // Token
-  getDeveloperToken: () => ipcRenderer.invoke('get-developer-token'),
-  refreshDeveloperToken: () => ipcRenderer.invoke('refresh-developer-token'),
-  isMusicKitConfigured: () => ipcRenderer.invoke('is-musickit-configured'),
+  getDeveloperToken: () => invokeCommand('get-developer-token'),
+  refreshDeveloperToken: () => invokeCommand('refresh-developer-token'),
+  isMusicKitConfigured: () => invokeCommand('is-musickit-configured'),
+  testDeveloperToken: () => invokeCommand('test-developer-token') as Promise<boolean>,
+  getSigningKeyFingerprint: () => invokeCommand('get-signing-key-fingerprint'),
+  getDeveloperTokenExpiry: () => invokeCommand('get-developer-token-expiry'),
+  rotatePrivateKey: (keyContent: string, keyId: string) =>
+    invokeCommand('rotate-private-key', keyContent, keyId) as Promise<{ persisted: boolean }>,
+  setMusicKitConfig: (teamId: string, keyId: string, keyContent: string, allowPlaintext?: boolean) =>
+    invokeCommand('set-musickit-config', teamId, keyId, keyContent, allowPlaintext) as Promise<void>,
+  getAuthDebugEnabled: () => invokeCommand('get-auth-debug-enabled') as Promise<boolean>,
+  setAuthDebugEnabled: (enabled: boolean) => invokeCommand('set-auth-debug-enabled', enabled) as Promise<boolean>,
+  getAuthDebugTrace: () => invokeCommand('get-auth-debug-trace'),
+  onDeveloperTokenRefreshed: (callback: (token: string) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, token: string) => callback(token);
+    ipcRenderer.on('developer-token-refreshed', handler);
+    return () => ipcRenderer.removeListener('developer-token-refreshed', handler);
+  },
+  onDeveloperTokenExpired: (callback: (event: { exp: number | null }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, payload: { exp: number | null }) => callback(payload);
+    ipcRenderer.on('developer-token-expired', handler);
+    return () => ipcRenderer.removeListener('developer-token-expired', handler);
+  },
+
+  // MusicKit
+  getMusicKitInitConfig: () => invokeCommand('get-musickit-init-config'),
+  setContentRatingFilter: (mode: 'explicit' | 'clean') => ipcRenderer.invoke('set-content-rating-filter', mode),
+  onContentFilterChanged: (callback: (mode: 'explicit' | 'clean') => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, mode: 'explicit' | 'clean') => callback(mode);
+    ipcRenderer.on('content-filter-changed', handler);
+    return () => ipcRenderer.removeListener('content-filter-changed', handler);
+  },
+
+  // API
+  getApiBaseUrl: () => ipcRenderer.invoke('get-api-base-url') as Promise<string>,
+  setApiBaseUrl: (url: string) => ipcRenderer.invoke('set-api-base-url', url) as Promise<string>,
 
   // Auth
-  openAuthWindow: (authUrl: string) => ipcRenderer.invoke('open-auth-window', authUrl),
+  openAuthWindow: (authUrl: string) => invokeCommand('open-auth-window', authUrl),
 
   // Windows
-  openMiniPlayer: () => ipcRenderer.invoke('open-mini-player'),
-  closeMiniPlayer: () => ipcRenderer.invoke('close-mini-player'),
-  hideMainWindow: () => ipcRenderer.invoke('hide-main-window'),
-  showMainWindow: () => ipcRenderer.invoke('show-main-window'),
+  openMiniPlayer: () => invokeCommand('open-mini-player'),
+  closeMiniPlayer: () => invokeCommand('close-mini-player'),
+  getMiniPlayerArtAction: () => invokeCommand('get-mini-player-art-action'),
+  setMiniPlayerArtAction: (action: 'openMain' | 'openLyrics' | 'none') =>
+    invokeCommand('set-mini-player-art-action', action),
+  miniPlayerArtClicked: () => invokeCommand('mini-player-art-clicked'),
+  setMiniPlayerFullscreenBehavior: (showOverFullscreen: boolean) =>
+    invokeCommand('set-mini-player-fullscreen-behavior', showOverFullscreen),
+  setWindowMinSize: (label: string, width: number, height: number) =>
+    invokeCommand('set-window-min-size', label, width, height),
+  setTrafficLightInset: (label: string, x: number, y: number) =>
+    invokeCommand('set-traffic-light-inset', label, x, y) as Promise<{ x: number; y: number }>,
+  frontendReady: () => invokeCommand('frontend-ready'),
+  hideMainWindow: () => invokeCommand('hide-main-window'),
+  showMainWindow: () => invokeCommand('show-main-window'),
 
   // Settings
   setCloseBehavior: (miniPlayer: boolean) => ipcRenderer.invoke('set-close-behavior', miniPlayer),
   setOpenAtLogin: (value: boolean) => ipcRenderer.invoke('set-open-at-login', value),
   getOpenAtLogin: () => ipcRenderer.invoke('get-open-at-login') as Promise<boolean>,
+  getLanguage: () => ipcRenderer.invoke('get-language') as Promise<string>,
+  setLanguage: (code: string | null) => ipcRenderer.invoke('set-language', code) as Promise<string>,
 
   // Discord
-  discordConnect: () => ipcRenderer.invoke('discord-connect'),
-  discordDisconnect: () => ipcRenderer.invoke('discord-disconnect'),
+  discordConnect: (appId: string) => invokeCommand('discord-connect', appId),
+  discordDisconnect: () => invokeCommand('discord-disconnect'),
   discordSetActivity: (params: {
     details: string;
     state: string;
@@ -32,13 +91,181 @@ contextBridge.exposeInMainWorld('electron', {
     smallImageText?: string;
     startTimestamp?: number;
     endTimestamp?: number;
-  }) => ipcRenderer.invoke('discord-set-activity', params),
-  discordClearActivity: () => ipcRenderer.invoke('discord-clear-activity'),
+    isPaused?: boolean;
+    trackUrl?: string;
+  }) => invokeCommand('discord-set-activity', params),
+  discordClearActivity: () => invokeCommand('discord-clear-activity'),
+  getDiscordPauseGrace: () => invokeCommand('get-discord-pause-grace') as Promise<number>,
+  setDiscordPauseGrace: (seconds: number) => invokeCommand('set-discord-pause-grace', seconds) as Promise<number>,
+  getDiscordShowWhilePaused: () => invokeCommand('get-discord-show-while-paused') as Promise<boolean>,
+  setDiscordShowWhilePaused: (enabled: boolean) =>
+    invokeCommand('set-discord-show-while-paused', enabled) as Promise<boolean>,
+
+  // Setup
+  completeSetup: () => ipcRenderer.invoke('complete-setup'),
+
+  // Events
+  replayEvents: () => ipcRenderer.invoke('replay-events') as Promise<Record<string, unknown>>,
+
+  // Health
+  startHealthServer: (port: number) => ipcRenderer.invoke('start-health-server', port) as Promise<number>,
+  stopHealthServer: () => ipcRenderer.invoke('stop-health-server'),
+
+  // Remote Control
+  startRemoteControl: (port?: number) =>
+    invokeCommand('start-remote-control', port) as Promise<{ code: string; url: string }>,
+  stopRemoteControl: () => invokeCommand('stop-remote-control'),
+
+  // Branding
+  getBranding: () => ipcRenderer.invoke('get-branding'),
+
+  // Playback
+  checkPlaybackPrerequisites: () => ipcRenderer.invoke('check-playback-prerequisites'),
+  setPlayerState: (state: { trackId: string | null; positionMs: number; durationMs: number; isPlaying: boolean }) =>
+    ipcRenderer.invoke('set-player-state', state),
+  getPlayerState: () => ipcRenderer.invoke('get-player-state'),
+  requestSeek: (positionMs: number) => ipcRenderer.invoke('request-seek', positionMs) as Promise<number>,
+  onMediaCommand: (callback: (command: { type: string; position_ms?: number }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, command: { type: string; position_ms?: number }) =>
+      callback(command);
+    ipcRenderer.on('media-command', handler);
+    return () => ipcRenderer.removeListener('media-command', handler);
+  },
+  getPlaybackSnapshot: () => ipcRenderer.invoke('get-playback-snapshot'),
+  setPositionSaveInterval: (seconds: number) => ipcRenderer.invoke('set-position-save-interval', seconds),
+  applyQueueUpdate: (update: unknown) => ipcRenderer.invoke('apply-queue-update', update),
+  getQueue: () => ipcRenderer.invoke('get-queue'),
+  onQueueUpdate: (callback: (update: unknown) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, update: unknown) => callback(update);
+    ipcRenderer.on('queue-update', handler);
+    return () => ipcRenderer.removeListener('queue-update', handler);
+  },
+  setTrackDurations: (durations: Record<string, number>) => ipcRenderer.invoke('set-track-durations', durations),
+  getCrossfadeWindow: (trackId: string) => ipcRenderer.invoke('get-crossfade-window', trackId),
+  onCrossfadeStart: (callback: (event: { next_index: number | null }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, payload: { next_index: number | null }) => callback(payload);
+    ipcRenderer.on('crossfade-start', handler);
+    return () => ipcRenderer.removeListener('crossfade-start', handler);
+  },
+  onTrackNearEnd: (callback: (event: { track_id: string; remaining_ms: number }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, payload: { track_id: string; remaining_ms: number }) =>
+      callback(payload);
+    ipcRenderer.on('track-near-end', handler);
+    return () => ipcRenderer.removeListener('track-near-end', handler);
+  },
+  getCrossfadeSeconds: () => ipcRenderer.invoke('get-crossfade-seconds') as Promise<number>,
+  setCrossfadeSeconds: (seconds: number) => ipcRenderer.invoke('set-crossfade-seconds', seconds) as Promise<number>,
+  scrobbleEligibility: (trackDurationMs: number, positionMs: number) =>
+    invokeCommand('scrobble-eligibility', trackDurationMs, positionMs),
+  getScrobbleFullOnly: () => invokeCommand('get-scrobble-full-only') as Promise<boolean>,
+  setScrobbleFullOnly: (enabled: boolean) => invokeCommand('set-scrobble-full-only', enabled) as Promise<boolean>,
+  getAutoplay: () => ipcRenderer.invoke('get-autoplay') as Promise<boolean>,
+  setAutoplay: (enabled: boolean) => ipcRenderer.invoke('set-autoplay', enabled) as Promise<boolean>,
+  reportQueueEnded: (lastTrackId: string | null) =>
+    ipcRenderer.invoke('report-queue-ended', lastTrackId) as Promise<boolean>,
+  onAutoplayChanged: (callback: (enabled: boolean) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, enabled: boolean) => callback(enabled);
+    ipcRenderer.on('autoplay-changed', handler);
+    return () => ipcRenderer.removeListener('autoplay-changed', handler);
+  },
+  onRequestAutoplaySeed: (callback: (seed: { last_track_id: string }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, seed: { last_track_id: string }) => callback(seed);
+    ipcRenderer.on('request-autoplay-seed', handler);
+    return () => ipcRenderer.removeListener('request-autoplay-seed', handler);
+  },
+  getPlaybackModes: () => ipcRenderer.invoke('get-playback-modes'),
+  setPlaybackModes: (shuffle: boolean, repeat: 'off' | 'one' | 'all') =>
+    ipcRenderer.invoke('set-playback-modes', shuffle, repeat),
+  onPlaybackModesRestored: (callback: (modes: { shuffle: boolean; repeat: 'off' | 'one' | 'all' }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, modes: { shuffle: boolean; repeat: 'off' | 'one' | 'all' }) =>
+      callback(modes);
+    ipcRenderer.on('playback-modes-restored', handler);
+    return () => ipcRenderer.removeListener('playback-modes-restored', handler);
+  },
+
+  // Artwork
+  getCachedArtwork: (url: string | null, size?: number) =>
+    ipcRenderer.invoke('get-cached-artwork', url, size) as Promise<string>,
+  resolveArtworkUrl: (url: string, size?: number) =>
+    invokeCommand('resolve-artwork-url', url, size) as Promise<string>,
+  getArtworkQuality: () => invokeCommand('get-artwork-quality'),
+  setArtworkQuality: (quality: 'low' | 'medium' | 'high') => invokeCommand('set-artwork-quality', quality),
+  setNetworkMetered: (metered: boolean) => invokeCommand('set-network-metered', metered),
+  onArtworkQualityChanged: (callback: (quality: 'low' | 'medium' | 'high') => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, quality: 'low' | 'medium' | 'high') => callback(quality);
+    ipcRenderer.on('artwork-quality-changed', handler);
+    return () => ipcRenderer.removeListener('artwork-quality-changed', handler);
+  },
+  setFallbackArtwork: (path: string | null) =>
+    ipcRenderer.invoke('set-fallback-artwork', path) as Promise<string | null>,
+  repairArtworkCache: () => ipcRenderer.invoke('repair-artwork-cache'),
+  cacheUsage: () => invokeCommand('cache-usage'),
+  setCacheBudget: (bytes: number) => invokeCommand('set-cache-budget', bytes),
+  prefetchPalettes: (urls: string[]) => ipcRenderer.invoke('prefetch-palettes', urls) as Promise<number>,
+  setActiveArtwork: (url: string) => ipcRenderer.invoke('set-active-artwork', url),
+  getActivePalette: () => ipcRenderer.invoke('get-active-palette'),
+
+  // Library
+  cacheLibrarySnapshot: (items: Array<{ id: string; type: 'album' | 'playlist'; title: string; artworkUrl?: string }>) =>
+    ipcRenderer.invoke('cache-library-snapshot', items),
+  getLibrarySnapshot: () => ipcRenderer.invoke('get-library-snapshot'),
+  importAppleMusicExport: (path: string) =>
+    invokeCommand('import-apple-music-export', path) as Promise<{
+      entries: Array<{ played_at: number; track: string; artist: string; ms_played: number; source: 'apple-export' }>;
+      skipped: number;
+    }>,
+
+  // Audio Output
+  listOutputDevices: () => ipcRenderer.invoke('list-output-devices'),
+  reportOutputDevices: (devices: Array<{ id: string; label: string }>, currentId: string | null) =>
+    ipcRenderer.invoke('report-output-devices', devices, currentId),
+  onSetOutputDevice: (callback: (deviceId: string) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, deviceId: string) => callback(deviceId);
+    ipcRenderer.on('set-output-device', handler);
+    return () => ipcRenderer.removeListener('set-output-device', handler);
+  },
+  onAudioRouteChanged: (
+    callback: (route: { devices: Array<{ id: string; label: string }>; current_device_id: string | null }) => void,
+  ) => {
+    const handler = (
+      _event: Electron.IpcRendererEvent,
+      route: { devices: Array<{ id: string; label: string }>; current_device_id: string | null },
+    ) => callback(route);
+    ipcRenderer.on('audio-route-changed', handler);
+    return () => ipcRenderer.removeListener('audio-route-changed', handler);
+  },
+
+  // Recap
+  generateListeningRecap: (trackArts: Array<{ url: string; title: string; artist: string }>, title: string) =>
+    ipcRenderer.invoke('generate-listening-recap', trackArts, title) as Promise<string>,
+
+  // Performance
+  getProcessStats: () => ipcRenderer.invoke('get-process-stats'),
+  setPerfOverlay: (enabled: boolean) => ipcRenderer.invoke('set-perf-overlay', enabled),
+  onProcessStats: (callback: (stats: { rss_bytes: number; cpu_percent: number; thread_count: number | null }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, stats: { rss_bytes: number; cpu_percent: number; thread_count: number | null }) => callback(stats);
+    ipcRenderer.on('process-stats', handler);
+    return () => ipcRenderer.removeListener('process-stats', handler);
+  },
+
+  // Telemetry
+  setTelemetry: (enabled: boolean, endpoint?: string) => ipcRenderer.invoke('set-telemetry', enabled, endpoint),
+  getTelemetryStatus: () => ipcRenderer.invoke('get-telemetry-status'),
+  recordTelemetryEvent: (name: string, count?: number) => ipcRenderer.invoke('record-telemetry-event', name, count),
+
+  // Logging
+  frontendLog: (level: string, message: string, context?: unknown) =>
+    ipcRenderer.invoke('frontend-log', level, message, context),
 
   // Global Shortcuts
   registerShortcut: (accelerator: string) => ipcRenderer.invoke('register-shortcut', accelerator),
   unregisterShortcut: (accelerator: string) => ipcRenderer.invoke('unregister-shortcut', accelerator),
+  setShortcuts: (bindings: Record<string, string>) =>
+    invokeCommand('set-shortcuts', bindings) as Promise<{ failed: string[] }>,
+  getEffectiveShortcuts: () => invokeCommand('get-effective-shortcuts'),
   unregisterAllShortcuts: () => ipcRenderer.invoke('unregister-all-shortcuts'),
+  getMediaKeysEnabled: () => invokeCommand('get-media-keys-enabled') as Promise<boolean>,
+  setMediaKeysEnabled: (enabled: boolean) => invokeCommand('set-media-keys-enabled', enabled),
 
   // Mini Player mode events
   onEnterMiniPlayer: (callback: () => void) => {
@@ -51,6 +278,17 @@ contextBridge.exposeInMainWorld('electron', {
     ipcRenderer.on('exit-mini-player', handler);
     return () => ipcRenderer.removeListener('exit-mini-player', handler);
   },
+  onAudioInterruption: (callback: (event: { began: boolean; should_resume: boolean }) => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, payload: { began: boolean; should_resume: boolean }) =>
+      callback(payload);
+    ipcRenderer.on('audio-interruption', handler);
+    return () => ipcRenderer.removeListener('audio-interruption', handler);
+  },
+  onOpenLyrics: (callback: () => void) => {
+    const handler = () => callback();
+    ipcRenderer.on('open-lyrics', handler);
+    return () => ipcRenderer.removeListener('open-lyrics', handler);
+  },
 
   // Events (main → renderer)
   onAppleMusicToken: (callback: (token: string) => void) => {
@@ -4,6 +4,7 @@ import { useMusicKit } from './hooks/useMusicKit';
 import { useTrayEvents } from './hooks/useTrayEvents';
 import { useMediaKeys } from './hooks/useMediaKeys';
 import { useBrowserNotifications } from './hooks/useNotifications';
+import { useOutputDevices } from './hooks/useOutputDevices';
 import { themeService } from './services/themes';
 import { keyboardService, setupDefaultShortcuts } from './services/keyboard';
 import { playerStore } from './stores/player';
@@ -59,6 +60,7 @@ const AppLayout: Component<{ children?: any }> = (props) => {
   const [isQueueOpen, setIsQueueOpen] = createSignal(false);
   const [isNowPlayingOpen, setIsNowPlayingOpen] = createSignal(false);
   const [isMiniPlayerMode, setIsMiniPlayerMode] = createSignal(false);
+  const [lyricsRequest, setLyricsRequest] = createSignal(0);
   let videoContainerRef: HTMLDivElement | undefined;
 
   // Initialize MusicKit on app load
@@ -81,6 +83,12 @@ const AppLayout: Component<{ children?: any }> = (props) => {
     });
     keyboardService.init();
 
+    // Let the main process lower artwork quality when the user asks to save data
+    const connection = (navigator as Navigator & { connection?: EventTarget & { saveData?: boolean } }).connection;
+    const reportMetered = () => window.electron.setNetworkMetered(!!connection?.saveData);
+    reportMetered();
+    connection?.addEventListener('change', reportMetered);
+
     // Listen for mini player mode transitions from main process
     const unEnter = window.electron.onEnterMiniPlayer(() => {
       setIsMiniPlayerMode(true);
@@ -88,10 +96,16 @@ const AppLayout: Component<{ children?: any }> = (props) => {
     const unExit = window.electron.onExitMiniPlayer(() => {
       setIsMiniPlayerMode(false);
     });
+    const unLyrics = window.electron.onOpenLyrics(() => {
+      setIsNowPlayingOpen(true);
+      setLyricsRequest((n) => n + 1);
+    });
 
     onCleanup(() => {
       unEnter();
       unExit();
+      unLyrics();
+      connection?.removeEventListener('change', reportMetered);
     });
   });
 
@@ -104,6 +118,9 @@ const AppLayout: Component<{ children?: any }> = (props) => {
   // Set up notifications
   useBrowserNotifications();
 
+  // Report audio output devices to the tray submenu
+  useOutputDevices();
+
   const videoMode = () => {
     if (!playerStore.isVideoPlaying()) return 'hidden';
     return isMiniPlayerMode() ? 'mini' : 'full';
@@ -183,6 +200,7 @@ const AppLayout: Component<{ children?: any }> = (props) => {
               <NowPlayingView
                 isOpen={isNowPlayingOpen()}
                 onClose={() => setIsNowPlayingOpen(false)}
+                lyricsRequest={lyricsRequest()}
               />
             </Suspense>
 
@@ -1,7 +1,7 @@
 import { Component, createSignal, createResource, For, Show } from 'solid-js';
 import { useNavigate } from '@solidjs/router';
 import { playerStore } from '../../stores/player';
-import { storageService } from '../../services/storage';
+import { storageService, IMPORTED_HISTORY_TYPE } from '../../services/storage';
 import { searchAPI } from '../../services/api';
 import { formatDuration } from '../../lib/musickit';
 import LazyImage from '../LazyImage/LazyImage';
@@ -33,8 +33,15 @@ const PlayHistory: Component = () => {
     }
   );
 
-  const handlePlay = (entry: HistoryEntry) => {
-    playerStore.playMedia(entry.type, entry.id);
+  const handlePlay = async (entry: HistoryEntry) => {
+    if (entry.type !== IMPORTED_HISTORY_TYPE) {
+      playerStore.playMedia(entry.type, entry.id);
+      return;
+    }
+    // Imported from an Apple export: look the song up by name
+    const results = await searchAPI.search(`${entry.name ?? ''} ${entry.artistName ?? ''}`, ['songs'], 1).catch(() => null);
+    const song = results?.songs?.data?.[0];
+    if (song) playerStore.playMedia(song.type, song.id);
   };
 
   const handleClearHistory = async () => {
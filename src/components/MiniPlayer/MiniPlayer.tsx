@@ -52,7 +52,9 @@ const MiniPlayer: Component = () => {
               <img
                 src={formatArtworkUrl(nowPlaying()!.attributes.artwork, 300)}
                 alt={nowPlaying()?.attributes.name}
-                class="w-full h-full object-cover"
+                class="w-full h-full object-cover cursor-pointer"
+                style={{ "-webkit-app-region": "no-drag" }}
+                onClick={() => window.electron.miniPlayerArtClicked().catch(() => {})}
               />
             </Show>
             {/* Waveform overlay for audio playback */}
@@ -13,6 +13,8 @@ const LyricsView = lazy(() => import('../Lyrics/LyricsView'));
 interface NowPlayingViewProps {
   isOpen: boolean;
   onClose: () => void;
+  // Bumped to open straight into lyrics (mini player art click)
+  lyricsRequest?: number;
 }
 
 const NowPlayingView: Component<NowPlayingViewProps> = (props) => {
@@ -30,6 +32,10 @@ const NowPlayingView: Component<NowPlayingViewProps> = (props) => {
     }
   };
 
+  createEffect(() => {
+    if (props.lyricsRequest) setShowLyrics(true);
+  });
+
   // Extract dominant color from artwork
   createEffect(() => {
     const nowPlaying = state().nowPlaying;
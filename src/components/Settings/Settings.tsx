@@ -3,6 +3,8 @@ import { Palette } from 'lucide-solid';
 import { musicKitStore } from '../../stores/musickit';
 import { playerStore } from '../../stores/player';
 import { themeService } from '../../services/themes';
+import { discordService } from '../../services/discord';
+import { storageService } from '../../services/storage';
 import ThemeCustomizer from '../ThemeCustomizer/ThemeCustomizer';
 
 interface SettingsState {
@@ -25,6 +27,10 @@ const Settings: Component = () => {
   const [isMusicKitConfigured, setIsMusicKitConfigured] = createSignal(false);
   const [showThemeCustomizer, setShowThemeCustomizer] = createSignal(false);
   const [currentThemeName, setCurrentThemeName] = createSignal(themeService.getCurrentTheme().name);
+  const [discordEnabled, setDiscordEnabled] = createSignal(false);
+  const [discordAppId, setDiscordAppId] = createSignal('');
+  const [discordConnected, setDiscordConnected] = createSignal(false);
+  const [discordShowWhilePaused, setDiscordShowWhilePaused] = createSignal(true);
 
   onMount(async () => {
     // Check if MusicKit is properly configured
@@ -58,6 +64,25 @@ const Settings: Component = () => {
       // Ignore — may fail in dev
     }
 
+    // Sync autoplay from Electron (source of truth)
+    try {
+      const autoplay = await window.electron.getAutoplay();
+      setSettings((prev) => ({ ...prev, autoplay }));
+    } catch {
+      // Ignore
+    }
+
+    // Discord settings live in the storage service
+    try {
+      const stored = await storageService.getSettings();
+      setDiscordEnabled(stored.discordRichPresence);
+      setDiscordAppId(stored.discordAppId);
+      setDiscordConnected(discordService.isActive());
+      setDiscordShowWhilePaused(await window.electron.getDiscordShowWhilePaused());
+    } catch {
+      // Ignore
+    }
+
     // Sync close behavior to main process from localStorage
     try {
       await window.electron.setCloseBehavior(settings().miniPlayerOnClose);
@@ -87,6 +112,12 @@ const Settings: Component = () => {
     });
   };
 
+  const saveDiscordAppId = async (value: string) => {
+    if (value.trim() === discordService.getAppId()) return;
+    setDiscordAppId(value.trim());
+    setDiscordConnected(await discordService.setAppId(value));
+  };
+
   const handleSignOut = async () => {
     await musicKitStore.unauthorize();
   };
@@ -186,9 +217,8 @@ const Settings: Component = () => {
                   checked={settings().autoplay}
                   onChange={(e) => {
                     updateSetting('autoplay', e.currentTarget.checked);
-                    // Apply to MusicKit instance
-                    const mk = musicKitStore.instance() as any;
-                    if (mk) mk.autoplayEnabled = e.currentTarget.checked;
+                    // Main process persists it and applies it to MusicKit via autoplay-changed
+                    window.electron.setAutoplay(e.currentTarget.checked).catch(() => {});
                   }}
                   class="sr-only peer"
                 />
@@ -324,6 +354,85 @@ const Settings: Component = () => {
         </div>
       </section>
 
+      {/* Discord Section */}
+      <section class="mb-8">
+        <h2 class="text-sm font-semibold text-white/60 uppercase tracking-wider mb-4">Discord</h2>
+        <div class="bg-surface-secondary rounded-xl overflow-hidden divide-y divide-white/10">
+          <div class="p-4">
+            <div class="flex items-center justify-between">
+              <div>
+                <p class="text-white font-medium">Rich Presence</p>
+                <p class="text-sm text-white/60">Show what you're listening to on Discord</p>
+              </div>
+              <label class="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={discordEnabled()}
+                  onChange={async (e) => {
+                    const value = e.currentTarget.checked;
+                    setDiscordEnabled(value);
+                    await discordService.setEnabled(value);
+                    setDiscordConnected(discordService.isActive());
+                  }}
+                  class="sr-only peer"
+                />
+                <div class="w-11 h-6 bg-surface-tertiary peer-focus:outline-hidden rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:rounded-full after:h-5 after:w-5 after:transition-all peer-checked:bg-apple-red"></div>
+              </label>
+            </div>
+          </div>
+
+          <div class="p-4">
+            <div class="flex items-center justify-between gap-4">
+              <div>
+                <p class="text-white font-medium">Application ID</p>
+                <Show
+                  when={discordAppId()}
+                  fallback={
+                    <p class="text-sm text-yellow-400">
+                      Set your Discord application ID to enable Rich Presence
+                    </p>
+                  }
+                >
+                  <p class="text-sm text-white/60">
+                    {discordEnabled() && discordConnected() ? 'Connected' : 'From discord.com/developers/applications'}
+                  </p>
+                </Show>
+              </div>
+              <input
+                type="text"
+                inputmode="numeric"
+                placeholder="123456789012345678"
+                value={discordAppId()}
+                onChange={(e) => saveDiscordAppId(e.currentTarget.value)}
+                class="w-56 px-3 py-2 bg-surface-tertiary rounded-lg text-white text-sm focus:outline-hidden focus:ring-2 focus:ring-apple-red"
+              />
+            </div>
+          </div>
+
+          <div class="p-4">
+            <div class="flex items-center justify-between">
+              <div>
+                <p class="text-white font-medium">Show While Paused</p>
+                <p class="text-sm text-white/60">Keep your status for a few minutes after pausing</p>
+              </div>
+              <label class="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={discordShowWhilePaused()}
+                  onChange={async (e) => {
+                    const value = e.currentTarget.checked;
+                    setDiscordShowWhilePaused(value);
+                    await window.electron.setDiscordShowWhilePaused(value).catch(() => {});
+                  }}
+                  class="sr-only peer"
+                />
+                <div class="w-11 h-6 bg-surface-tertiary peer-focus:outline-hidden rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:rounded-full after:h-5 after:w-5 after:transition-all peer-checked:bg-apple-red"></div>
+              </label>
+            </div>
+          </div>
+        </div>
+      </section>
+
       {/* Keyboard Shortcuts */}
       <section class="mb-8">
         <h2 class="text-sm font-semibold text-white/60 uppercase tracking-wider mb-4">Keyboard Shortcuts</h2>
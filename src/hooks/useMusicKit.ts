@@ -5,6 +5,8 @@ import { libraryStore } from '../stores/library';
 export function useMusicKit() {
   onMount(async () => {
     await musicKitStore.initialize();
+    // Main keeps the window hidden until now (initialize() never throws)
+    window.electron.frontendReady().catch(() => {});
   });
 
   // Fetch library when authorized
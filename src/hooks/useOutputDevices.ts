@@ -0,0 +1,84 @@
+import { onMount, onCleanup } from 'solid-js';
+
+const STORAGE_KEY = 'output-device';
+
+type SinkElement = HTMLMediaElement & { sinkId?: string; setSinkId?: (id: string) => Promise<void> };
+
+/**
+ * Hook to keep the main process informed about audio output devices
+ * and to apply device selections made from the tray.
+ *
+ * Chromium only exposes sinks to the page, so enumeration and setSinkId
+ * happen here; the main process just renders the tray submenu.
+ */
+export function useOutputDevices() {
+  let unlistenSetDevice: (() => void) | undefined;
+
+  onMount(() => {
+    if (!navigator.mediaDevices?.enumerateDevices) return;
+
+    reportDevices();
+    navigator.mediaDevices.addEventListener('devicechange', reportDevices);
+
+    // MusicKit may create new media elements per item — apply the chosen
+    // sink whenever any element starts playing (play doesn't bubble, so capture)
+    document.addEventListener('play', handleMediaPlay, true);
+
+    unlistenSetDevice = window.electron.onSetOutputDevice(async (deviceId) => {
+      localStorage.setItem(STORAGE_KEY, deviceId);
+      await applySinkToAll(deviceId);
+      reportDevices();
+    });
+  });
+
+  onCleanup(() => {
+    navigator.mediaDevices?.removeEventListener('devicechange', reportDevices);
+    document.removeEventListener('play', handleMediaPlay, true);
+    unlistenSetDevice?.();
+  });
+}
+
+function getSelectedDevice(): string | null {
+  return localStorage.getItem(STORAGE_KEY);
+}
+
+async function reportDevices(): Promise<void> {
+  try {
+    const all = await navigator.mediaDevices.enumerateDevices();
+    const outputs = all
+      .filter((d) => d.kind === 'audiooutput')
+      .map((d, i) => ({ id: d.deviceId, label: d.label || `Output ${i + 1}` }));
+
+    // Forget a selection whose device was unplugged
+    let selected = getSelectedDevice();
+    if (selected && !outputs.some((d) => d.id === selected)) {
+      localStorage.removeItem(STORAGE_KEY);
+      selected = null;
+    }
+
+    await window.electron.reportOutputDevices(outputs, selected);
+  } catch (err) {
+    console.warn('[Tuffahi] Failed to enumerate output devices:', err);
+  }
+}
+
+async function applySink(el: SinkElement, deviceId: string): Promise<void> {
+  if (typeof el.setSinkId !== 'function' || el.sinkId === deviceId) return;
+  try {
+    await el.setSinkId(deviceId);
+  } catch (err) {
+    console.warn('[Tuffahi] setSinkId failed:', err);
+  }
+}
+
+async function applySinkToAll(deviceId: string): Promise<void> {
+  const elements = Array.from(document.querySelectorAll<SinkElement>('audio, video'));
+  await Promise.all(elements.map((el) => applySink(el, deviceId)));
+}
+
+function handleMediaPlay(event: Event): void {
+  const deviceId = getSelectedDevice();
+  if (deviceId && event.target instanceof HTMLMediaElement) {
+    applySink(event.target as SinkElement, deviceId);
+  }
+}
@@ -8,6 +8,8 @@ export function useTrayEvents() {
   let unlistenPlayPause: (() => void) | undefined;
   let unlistenNext: (() => void) | undefined;
   let unlistenPrevious: (() => void) | undefined;
+  let unlistenMediaCommand: (() => void) | undefined;
+  let unlistenInterruption: (() => void) | undefined;
 
   onMount(() => {
     // Listen for play/pause from tray
@@ -24,11 +26,40 @@ export function useTrayEvents() {
     unlistenPrevious = window.electron.onTrayPrevious(() => {
       playerStore.skipPrevious();
     });
+
+    // Commands from the main process (request-seek, LAN remote)
+    unlistenMediaCommand = window.electron.onMediaCommand((command) => {
+      switch (command.type) {
+        case 'Seek':
+          playerStore.seekTo(command.position_ms / 1000);
+          break;
+        case 'PlayPause':
+          playerStore.togglePlayPause();
+          break;
+        case 'Next':
+          playerStore.skipNext();
+          break;
+        case 'Previous':
+          playerStore.skipPrevious();
+          break;
+      }
+    });
+
+    // System interruptions (suspend/resume) — pause, and resume if we were playing
+    unlistenInterruption = window.electron.onAudioInterruption(({ began, should_resume }) => {
+      if (began) {
+        playerStore.pause();
+      } else if (should_resume) {
+        playerStore.play();
+      }
+    });
   });
 
   onCleanup(() => {
     unlistenPlayPause?.();
     unlistenNext?.();
     unlistenPrevious?.();
+    unlistenMediaCommand?.();
+    unlistenInterruption?.();
   });
 }
@@ -7,15 +7,21 @@ import { logger } from './logger';
 import { storageService } from './storage';
 import type { DiscordPresence } from '../types';
 
+function isAppError(error: unknown): error is AppError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error;
+}
+
 class DiscordService {
   private isConnected = false;
   private isEnabled = false;
+  private appId = '';
   private updateInterval: number | undefined;
   private currentPresence: DiscordPresence | null = null;
 
   async init(): Promise<void> {
     const settings = await storageService.getSettings();
     this.isEnabled = settings.discordRichPresence;
+    this.appId = settings.discordAppId;
 
     if (this.isEnabled) {
       await this.connect();
@@ -24,9 +30,14 @@ class DiscordService {
 
   async connect(): Promise<boolean> {
     if (this.isConnected) return true;
+    // Nothing to connect as until the user enters their application ID
+    if (!this.appId) {
+      logger.info('discord', 'Discord application ID not set, skipping connect');
+      return false;
+    }
 
     try {
-      await window.electron.discordConnect();
+      await window.electron.discordConnect(this.appId);
       this.isConnected = true;
       logger.info('discord', 'Connected to Discord');
       return true;
@@ -69,10 +80,33 @@ class DiscordService {
     }
   }
 
+  /** Saves the application ID and reconnects under it. Returns whether Discord is now connected. */
+  async setAppId(appId: string): Promise<boolean> {
+    this.appId = appId.trim();
+    await storageService.saveSettings({ discordAppId: this.appId });
+
+    if (!this.isEnabled) return false;
+    await this.disconnect();
+    const connected = await this.connect();
+    if (connected && this.currentPresence) {
+      await this.updatePresence(this.currentPresence);
+    }
+    return connected;
+  }
+
+  getAppId(): string {
+    return this.appId;
+  }
+
+  isPresenceEnabled(): boolean {
+    return this.isEnabled;
+  }
+
   async updatePresence(presence: DiscordPresence): Promise<void> {
     this.currentPresence = presence;
 
-    if (!this.isEnabled || !this.isConnected) return;
+    // Main queues the activity if the connection isn't up yet
+    if (!this.isEnabled) return;
 
     try {
       await window.electron.discordSetActivity({
@@ -84,9 +118,17 @@ class DiscordService {
         smallImageText: presence.smallImageText,
         startTimestamp: presence.startTimestamp,
         endTimestamp: presence.endTimestamp,
+        isPaused: presence.isPaused,
+        trackUrl: presence.trackUrl,
       });
       logger.debug('discord', 'Presence updated', presence);
     } catch (error) {
+      // Not connected (yet, or any more): main keeps it and applies it on connect
+      if (isAppError(error) && error.code === 'DISCORD_NOT_CONNECTED') {
+        this.isConnected = false;
+        logger.debug('discord', 'Presence queued until Discord connects');
+        return;
+      }
       logger.warn('discord', 'Failed to update presence', { error });
     }
   }
@@ -110,6 +152,7 @@ class DiscordService {
     artistName: string;
     albumName?: string;
     artworkUrl?: string;
+    url?: string;
     durationMs?: number;
     currentTimeMs?: number;
   }): DiscordPresence {
@@ -120,6 +163,7 @@ class DiscordService {
       largeImageText: track.albumName || track.name,
       smallImageKey: 'playing',
       smallImageText: 'Playing',
+      trackUrl: track.url,
     };
 
     // Add timestamps for progress
@@ -138,6 +182,7 @@ class DiscordService {
     artistName: string;
     albumName?: string;
     artworkUrl?: string;
+    url?: string;
   }): DiscordPresence {
     return {
       details: track.name,
@@ -146,6 +191,8 @@ class DiscordService {
       largeImageText: track.albumName || track.name,
       smallImageKey: 'paused',
       smallImageText: 'Paused',
+      isPaused: true,
+      trackUrl: track.url,
     };
   }
 
@@ -5,6 +5,11 @@
 
 import { logger } from './logger';
 import { storageService } from './storage';
+import { toast } from '../components/Toast/Toast';
+
+function isAppError(error: unknown): error is AppError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error;
+}
 
 export interface KeyboardShortcut {
   id: string;
@@ -18,7 +23,6 @@ export interface KeyboardShortcut {
 
 class KeyboardService {
   private shortcuts: Map<string, KeyboardShortcut> = new Map();
-  private registeredKeys: Set<string> = new Set();
   private isEnabled = true;
   private unlistenGlobalShortcut: (() => void) | null = null;
 
@@ -72,40 +76,40 @@ class KeyboardService {
     this.shortcuts.set(shortcut.id, fullShortcut);
   }
 
-  async registerAll(): Promise<void> {
-    if (!this.isEnabled) return;
+  async registerAll(): Promise<boolean> {
+    if (!this.isEnabled) return true;
 
+    const bindings: Record<string, string> = {};
     for (const shortcut of this.shortcuts.values()) {
-      await this.registerKey(shortcut);
+      bindings[shortcut.id] = shortcut.currentKey;
     }
-  }
 
-  async unregisterAll(): Promise<void> {
     try {
-      await window.electron.unregisterAllShortcuts();
-      this.registeredKeys.clear();
-      logger.info('keyboard', 'All shortcuts unregistered');
+      // Main process rejects the whole map if two actions share a key
+      const { failed } = await window.electron.setShortcuts(bindings);
+      if (failed.length > 0) {
+        logger.warn('keyboard', 'Some shortcuts could not be registered', { failed });
+      }
+      return true;
     } catch (error) {
-      logger.error('keyboard', 'Failed to unregister shortcuts', { error });
+      if (isAppError(error) && error.code === 'DUPLICATE_BINDING') {
+        const { accelerator, actions } = error.details as { accelerator: string; actions: string[] };
+        const names = actions.map((id) => this.shortcuts.get(id)?.name ?? id);
+        logger.warn('keyboard', 'Duplicate shortcut binding', { accelerator, actions });
+        toast.warning('Shortcut conflict', `${accelerator} is assigned to ${names.join(' and ')}`);
+        return false;
+      }
+      logger.error('keyboard', 'Failed to register shortcuts', { error });
+      return false;
     }
   }
 
-  private async registerKey(shortcut: KeyboardShortcut): Promise<boolean> {
-    if (this.registeredKeys.has(shortcut.currentKey)) {
-      return true;
-    }
-
+  async unregisterAll(): Promise<void> {
     try {
-      const success = await window.electron.registerShortcut(shortcut.currentKey);
-
-      if (success) {
-        this.registeredKeys.add(shortcut.currentKey);
-        logger.debug('keyboard', 'Shortcut registered', { id: shortcut.id, key: shortcut.currentKey });
-      }
-      return success;
+      await window.electron.unregisterAllShortcuts();
+      logger.info('keyboard', 'All shortcuts unregistered');
     } catch (error) {
-      logger.warn('keyboard', 'Failed to register shortcut', { id: shortcut.id, key: shortcut.currentKey, error });
-      return false;
+      logger.error('keyboard', 'Failed to unregister shortcuts', { error });
     }
   }
 
@@ -113,22 +117,15 @@ class KeyboardService {
     const shortcut = this.shortcuts.get(id);
     if (!shortcut) return false;
 
-    // Unregister old key
-    if (this.registeredKeys.has(shortcut.currentKey)) {
-      try {
-        await window.electron.unregisterShortcut(shortcut.currentKey);
-        this.registeredKeys.delete(shortcut.currentKey);
-      } catch (error) {
-        logger.warn('keyboard', 'Failed to unregister old shortcut', { id, key: shortcut.currentKey });
-      }
-    }
-
-    // Update and register new key
+    // Re-register the whole map; on a duplicate the old key stays in place
+    const oldKey = shortcut.currentKey;
     shortcut.currentKey = newKey;
-    const success = await this.registerKey(shortcut);
+    const success = await this.registerAll();
 
     if (success) {
       await this.saveCustomBindings();
+    } else {
+      shortcut.currentKey = oldKey;
     }
 
     return success;
@@ -205,6 +202,7 @@ class KeyboardService {
 export const keyboardService = new KeyboardService();
 
 // Register global media key shortcuts (system-wide, work when app is in background).
+// Defaults are mirrored in electron/main/shortcuts.ts (DEFAULT_SHORTCUTS).
 // In-window keyboard shortcuts (Space, Ctrl+Arrows, Ctrl+M) are handled separately
 // by useMediaKeys hook via document keydown listeners.
 export function setupDefaultShortcuts(playerActions: {
@@ -86,6 +86,11 @@ class Logger {
     // Notify subscribers
     this.subscribers.forEach(callback => callback(entry));
 
+    // Forward to the main-process log file (rate-limited there)
+    if (level !== 'debug') {
+      this.forwardToMain(entry);
+    }
+
     // Persist if enabled
     if (this.persistLogs && level !== 'debug') {
       this.persistLog(entry);
@@ -144,6 +149,15 @@ class Logger {
       .join('\n');
   }
 
+  private forwardToMain(entry: LogEntry): void {
+    try {
+      window.electron?.frontendLog(entry.level, `[${entry.category}] ${entry.message}`, entry.data)
+        .catch(() => {});
+    } catch {
+      // Context data may not be cloneable over IPC — drop it silently
+    }
+  }
+
   private persistLog(entry: LogEntry): void {
     try {
       const stored = localStorage.getItem(PERSIST_KEY);
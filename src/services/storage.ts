@@ -8,6 +8,12 @@ import type { AppSettings, EqualizerBands, CustomTheme } from '../types';
 const DB_NAME = 'apple-music-storage';
 const DB_VERSION = 1;
 
+// Newest entries kept; one entry per track
+const PLAY_HISTORY_LIMIT = 100;
+
+// History entries seeded from an Apple Music export have no catalog id
+export const IMPORTED_HISTORY_TYPE = 'imported';
+
 const STORES = {
   SETTINGS: 'settings',
   THEMES: 'themes',
@@ -110,6 +116,7 @@ class StorageService {
       equalizerPreset: 'flat',
       equalizerCustom: this.getDefaultEqualizerBands(),
       discordRichPresence: false,
+      discordAppId: '',
       sleepTimer: { enabled: false, duration: 30, endOfTrack: false, fadeOut: true },
       keyboardShortcutsEnabled: true,
       customKeyBindings: {},
@@ -187,10 +194,41 @@ class StorageService {
   }): Promise<void> {
     const history = await this.getPlayHistory();
     const filtered = history.filter(h => h.id !== entry.id);
-    const updated = [{ ...entry, playedAt: Date.now() }, ...filtered].slice(0, 100);
+    const updated = [{ ...entry, playedAt: Date.now() }, ...filtered].slice(0, PLAY_HISTORY_LIMIT);
     await this.dbSet(STORES.PLAY_HISTORY, 'history', updated);
   }
 
+  /**
+   * Seeds play history from an Apple Music privacy export (parsed in main).
+   * Songs already in history, matched by name and artist, are skipped, and
+   * the usual cap applies, so only the newest plays overall are kept.
+   * `imported` counts the export entries that made it in.
+   */
+  async importAppleMusicExport(path: string): Promise<{ imported: number; skipped: number }> {
+    const { entries, skipped } = await window.electron.importAppleMusicExport(path);
+    const history = await this.getPlayHistory();
+    const songKey = (name = '', artistName = '') => `${name.toLowerCase()}|${artistName.toLowerCase()}`;
+    const known = new Set(history.map((h) => songKey(h.name, h.artistName)));
+
+    const imported = entries
+      .filter((entry) => !known.has(songKey(entry.track, entry.artist)))
+      .map((entry) => ({
+        id: `${IMPORTED_HISTORY_TYPE}:${songKey(entry.track, entry.artist)}`,
+        type: IMPORTED_HISTORY_TYPE,
+        playedAt: entry.played_at,
+        name: entry.track,
+        artistName: entry.artist,
+      }));
+
+    const merged = [...history, ...imported]
+      .sort((a, b) => b.playedAt - a.playedAt)
+      .slice(0, PLAY_HISTORY_LIMIT);
+    await this.dbSet(STORES.PLAY_HISTORY, 'history', merged);
+    const importedIds = new Set(imported.map((entry) => entry.id));
+    const kept = merged.filter((h) => importedIds.has(h.id)).length;
+    return { imported: kept, skipped: skipped + entries.length - imported.length };
+  }
+
   async clearPlayHistory(): Promise<void> {
     await this.dbDelete(STORES.PLAY_HISTORY, 'history');
   }
@@ -1,5 +1,6 @@
 import { createSignal, createRoot } from 'solid-js';
 import { musicKitStore } from './musickit';
+import { formatArtworkUrl } from '../lib/musickit';
 
 export interface LibraryState {
   songs: MusicKit.LibrarySong[];
@@ -13,6 +14,8 @@ export interface LibraryState {
 
 export interface LibraryStore {
   state: () => LibraryState;
+  snapshot: () => LibrarySnapshot | null;
+  loadSnapshot: () => Promise<void>;
   fetchSongs: (limit?: number) => Promise<void>;
   fetchAlbums: (limit?: number) => Promise<void>;
   fetchArtists: (limit?: number) => Promise<void>;
@@ -31,6 +34,8 @@ function createLibraryStore(): LibraryStore {
     isLoading: false,
     error: null,
   });
+  // Last-known albums/playlists, shown while offline
+  const [snapshot, setSnapshot] = createSignal<LibrarySnapshot | null>(null);
 
   async function fetchSongs(limit: number = 100): Promise<void> {
     const mk = musicKitStore.instance();
@@ -113,13 +118,48 @@ function createLibraryStore(): LibraryStore {
   }
 
   async function fetchAll(): Promise<void> {
+    if (!navigator.onLine) {
+      await loadSnapshot();
+      return;
+    }
+
     setState((prev) => ({ ...prev, isLoading: true, error: null }));
     await Promise.all([fetchSongs(), fetchAlbums(), fetchArtists(), fetchPlaylists(), fetchRecentlyAdded()]);
     setState((prev) => ({ ...prev, isLoading: false }));
+    saveSnapshot();
+  }
+
+  function saveSnapshot(): void {
+    const { albums, playlists } = state();
+    const toItem = (item: MusicKit.LibraryAlbum | MusicKit.LibraryPlaylist, type: 'album' | 'playlist') => ({
+      id: item.id,
+      type,
+      title: item.attributes?.name ?? '',
+      artworkUrl: item.attributes?.artwork ? formatArtworkUrl(item.attributes.artwork, 300) : undefined,
+    });
+    const items = [
+      ...albums.map((a) => toItem(a, 'album')),
+      ...playlists.map((p) => toItem(p, 'playlist')),
+    ];
+    if (items.length === 0) return;
+
+    window.electron.cacheLibrarySnapshot(items)
+      .then(setSnapshot)
+      .catch((err: unknown) => console.warn('[Library] Failed to cache snapshot:', err));
+  }
+
+  async function loadSnapshot(): Promise<void> {
+    try {
+      setSnapshot(await window.electron.getLibrarySnapshot());
+    } catch (err) {
+      console.warn('[Library] Failed to load snapshot:', err);
+    }
   }
 
   return {
     state,
+    snapshot,
+    loadSnapshot,
     fetchSongs,
     fetchAlbums,
     fetchArtists,
@@ -13,6 +13,11 @@ export interface MusicKitStore {
   unauthorize: () => Promise<void>;
 }
 
+// MusicKit JS does the actual filtering; restrictedEnabled hides explicit items
+function applyContentRating(mk: MusicKit.MusicKitInstance, mode: ContentRatingFilter): void {
+  (mk as unknown as Record<string, unknown>).restrictedEnabled = mode === 'clean';
+}
+
 function createMusicKitStore(): MusicKitStore {
   const [instance, setInstance] = createSignal<MusicKit.MusicKitInstance | null>(null);
   const [isConfigured, setIsConfigured] = createSignal(false);
@@ -93,10 +98,10 @@ function createMusicKitStore(): MusicKitStore {
           // Re-configure MusicKit so it picks up the token from localStorage
           console.log('[Tuffahi] Re-configuring MusicKit to pick up token...');
           const MK = await waitForMusicKit();
-          const devToken = await window.electron.getDeveloperToken();
+          const initConfig = await window.electron.getMusicKitInitConfig();
           const freshInstance = await MK.configure({
-            developerToken: devToken,
-            app: { name: 'Tuffahi', build: '1.0.0' },
+            developerToken: initConfig.developerToken,
+            app: initConfig.app,
           });
           setInstance(freshInstance);
           freshInstance.addEventListener('authorizationStatusDidChange', () => {
@@ -114,6 +119,26 @@ function createMusicKitStore(): MusicKitStore {
     }
   });
 
+  // A rotated signing key yields a new developer token — hand it to the live
+  // instance so requests keep working without a reload.
+  window.electron.onDeveloperTokenRefreshed((developerToken: string) => {
+    const mk = instance();
+    if (!mk) return;
+    (mk as unknown as Record<string, unknown>).developerToken = developerToken;
+    console.log('[Tuffahi] Developer token refreshed');
+  });
+
+  // The built-in token lapsed and there's nothing to re-sign with — surface it
+  // rather than leaving MusicKit to fail request by request
+  window.electron.onDeveloperTokenExpired(() => {
+    setError('The Apple Music developer token has expired. Save MusicKit credentials in Settings or update the app.');
+  });
+
+  window.electron.onContentFilterChanged((mode) => {
+    const mk = instance();
+    if (mk) applyContentRating(mk, mode);
+  });
+
   // Store mock window ref so MusicKit thinks the popup is open
   let authMockWindow: Record<string, unknown> | null = null;
 
@@ -139,15 +164,14 @@ function createMusicKitStore(): MusicKitStore {
       setError(null);
 
       const MK = await waitForMusicKit();
-      const developerToken = await window.electron.getDeveloperToken();
+      const initConfig = await window.electron.getMusicKitInitConfig();
+      const { developerToken } = initConfig;
 
       const musicKitInstance = await MK.configure({
         developerToken,
-        app: {
-          name: 'Tuffahi',
-          build: '1.0.0',
-        },
+        app: initConfig.app,
       });
+      applyContentRating(musicKitInstance, initConfig.contentRating);
 
       setInstance(musicKitInstance);
       setIsConfigured(true);
@@ -186,7 +210,7 @@ function createMusicKitStore(): MusicKitStore {
             console.log('[Tuffahi] Re-configuring MusicKit...');
             const freshInstance = await MK.configure({
               developerToken,
-              app: { name: 'Tuffahi', build: '1.0.0' },
+              app: initConfig.app,
             });
             setInstance(freshInstance);
             freshInstance.addEventListener('authorizationStatusDidChange', () => {
@@ -216,7 +240,13 @@ function createMusicKitStore(): MusicKitStore {
 
       console.log('MusicKit initialized, isAuthorized:', musicKitInstance.isAuthorized);
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : 'Failed to initialize MusicKit';
+      // Main-process commands reject with a plain { code, message } object
+      const errorMessage =
+        err instanceof Error
+          ? err.message
+          : typeof err === 'object' && err !== null && 'message' in err
+            ? String((err as AppError).message)
+            : 'Failed to initialize MusicKit';
       setError(errorMessage);
       console.error('MusicKit initialization error:', err);
     }
@@ -2,7 +2,7 @@ import { createSignal, createEffect, createRoot, onCleanup } from 'solid-js';
 import { musicKitStore } from './musickit';
 import { formatArtworkUrl } from '../lib/musickit';
 import { updateMediaSessionMetadata, updateMediaSessionPlaybackState, updateMediaSessionPositionState } from '../hooks/useMediaKeys';
-import { storageService } from '../services/storage';
+import { storageService, IMPORTED_HISTORY_TYPE } from '../services/storage';
 
 export type RepeatMode = 'none' | 'one' | 'all';
 export type ShuffleMode = 'off' | 'on';
@@ -106,8 +106,15 @@ function createPlayerStore(): PlayerStore {
       const isPlaying = event.state === MusicKit.PlaybackStates.playing;
       setState((prev) => ({ ...prev, isPlaying }));
       updateMediaSessionPlaybackState(isPlaying);
+      reportPlayerState();
       // Release play lock once playback actually starts or stops
       if (isPlaying) playLock = false;
+      // Queue ran out — main decides whether autoplay should pick up from here
+      if (event.state === MusicKit.PlaybackStates.completed) {
+        const { nowPlaying, queue } = state();
+        const lastTrackId = nowPlaying?.id ?? queue[queue.length - 1]?.id ?? null;
+        window.electron.reportQueueEnded(lastTrackId).catch(() => {});
+      }
     };
 
     const handleNowPlayingChange = (event: { item: MusicKit.MediaItem | null }) => {
@@ -136,6 +143,8 @@ function createPlayerStore(): PlayerStore {
         const { name, artistName, albumName, artwork, durationInMillis } = event.item.attributes;
         const artworkUrl = artwork ? formatArtworkUrl(artwork, 512) : undefined;
         updateMediaSessionMetadata(name, artistName ?? '', albumName ?? '', artworkUrl);
+        if (artworkUrl) window.electron.setActiveArtwork(artworkUrl).catch(() => {});
+        prefetchUpcomingPalettes(mk);
 
         // Record to local play history with metadata
         storageService.addToPlayHistory({
@@ -151,6 +160,7 @@ function createPlayerStore(): PlayerStore {
 
     const handleTimeChange = (event: { currentPlaybackTime: number }) => {
       setCurrentTime(event.currentPlaybackTime);
+      reportPlayerState();
       const dur = duration();
       if (dur > 0) {
         updateMediaSessionPositionState(dur, event.currentPlaybackTime);
@@ -171,6 +181,8 @@ function createPlayerStore(): PlayerStore {
       }
       const items = (event.items || []).filter((item) => item != null);
       setState((prev) => ({ ...prev, queue: items }));
+      reportQueue({ kind: 'replaced', items: items.map(toQueueSummary) });
+      reportDurations(items);
     };
 
     const handleVolumeChange = (event: { volume: number }) => {
@@ -197,18 +209,22 @@ function createPlayerStore(): PlayerStore {
       repeatMode: getRepeatModeFromMK((mk as any).repeatMode),
     }));
 
-    // Apply autoplay setting from localStorage
-    try {
-      const savedSettings = localStorage.getItem('app-settings');
-      if (savedSettings) {
-        const parsed = JSON.parse(savedSettings);
-        if (parsed.autoplay !== undefined) {
-          (mk as any).autoplayEnabled = parsed.autoplay;
-        }
-      }
-    } catch {
-      // Ignore parse errors
-    }
+    // Persisted shuffle/repeat take precedence over MusicKit's defaults
+    applyRestoredModes();
+
+    // Autoplay preference lives in the main process
+    window.electron.getAutoplay().then((enabled) => {
+      (mk as any).autoplayEnabled = enabled;
+    }).catch(() => {});
+    const unlistenAutoplay = window.electron.onAutoplayChanged((enabled) => {
+      (mk as any).autoplayEnabled = enabled;
+    });
+    const unlistenAutoplaySeed = window.electron.onRequestAutoplaySeed(({ last_track_id }) => {
+      // Song stations use the "ra." prefix on the song's catalog id
+      playStation(`ra.${last_track_id}`).catch((err) => {
+        console.warn('[Player] Autoplay station failed:', err);
+      });
+    });
 
     // Apply saved playback rate
     const savedRate = playbackRate();
@@ -228,6 +244,8 @@ function createPlayerStore(): PlayerStore {
       mk.removeEventListener('playbackDurationDidChange', handleDurationChange);
       mk.removeEventListener('queueItemsDidChange', handleQueueChange);
       mk.removeEventListener('playbackVolumeDidChange', handleVolumeChange);
+      unlistenAutoplay();
+      unlistenAutoplaySeed();
     });
   });
 
@@ -246,6 +264,35 @@ function createPlayerStore(): PlayerStore {
     }
   };
 
+  // Shuffle/repeat preferences restored by the main process on launch.
+  // Held until MusicKit is ready, then applied once.
+  let restoredModes: { shuffle: boolean; repeat: 'off' | 'one' | 'all' } | null = null;
+
+  window.electron.onPlaybackModesRestored((modes) => {
+    restoredModes = modes;
+    applyRestoredModes();
+  });
+
+  function applyRestoredModes(): void {
+    const mk = musicKitStore.instance() as any;
+    if (!mk || !restoredModes) return;
+
+    const shuffleMode: ShuffleMode = restoredModes.shuffle ? 'on' : 'off';
+    const repeatMode: RepeatMode = restoredModes.repeat === 'off' ? 'none' : restoredModes.repeat;
+    restoredModes = null;
+
+    mk.shuffleMode = shuffleMode === 'on' ? 1 : 0;
+    mk.repeatMode = repeatMode === 'one' ? 1 : repeatMode === 'all' ? 2 : 0;
+    setState((prev) => ({ ...prev, shuffleMode, repeatMode }));
+  }
+
+  function persistPlaybackModes(): void {
+    const { shuffleMode, repeatMode } = state();
+    window.electron
+      .setPlaybackModes(shuffleMode === 'on', repeatMode === 'none' ? 'off' : repeatMode)
+      .catch((err: unknown) => console.warn('[Player] Failed to persist playback modes:', err));
+  }
+
   function getRepeatModeFromMK(mode: number): RepeatMode {
     switch (mode) {
       case 1: return 'one';
@@ -331,6 +378,7 @@ function createPlayerStore(): PlayerStore {
     if (mk) {
       mk.shuffleMode = mode === 'on' ? 1 : 0;
       setState((prev) => ({ ...prev, shuffleMode: mode }));
+      persistPlaybackModes();
     }
   }
 
@@ -345,6 +393,7 @@ function createPlayerStore(): PlayerStore {
       const mkMode = mode === 'one' ? 1 : mode === 'all' ? 2 : 0;
       mk.repeatMode = mkMode;
       setState((prev) => ({ ...prev, repeatMode: mode }));
+      persistPlaybackModes();
     }
   }
 
@@ -551,6 +600,58 @@ function createPlayerStore(): PlayerStore {
     }
   }
 
+  // Mirror player state to the main process (position persistence, seek validation)
+  function reportPlayerState(): void {
+    const { nowPlaying, isPlaying } = state();
+    window.electron.setPlayerState({
+      trackId: nowPlaying?.id ?? null,
+      positionMs: Math.floor(currentTime() * 1000),
+      durationMs: Math.floor(duration() * 1000),
+      isPlaying,
+    }).catch(() => {});
+  }
+
+  // Mirror queue changes to the main process as diffs
+  function toQueueSummary(item: MusicKit.MediaItem): QueueItemSummary {
+    return {
+      id: item.id,
+      type: item.type,
+      title: item.attributes?.name,
+      artist: item.attributes?.artistName,
+    };
+  }
+
+  function reportQueue(update: QueueUpdate): void {
+    window.electron.applyQueueUpdate(update).catch(() => {});
+  }
+
+  // Durations feed the main process's crossfade window computation
+  function reportDurations(items: MusicKit.MediaItem[]): void {
+    const durations: Record<string, number> = {};
+    for (const item of items) {
+      const ms = item.attributes?.durationInMillis;
+      if (ms) durations[item.id] = ms;
+    }
+    if (Object.keys(durations).length > 0) {
+      window.electron.setTrackDurations(durations).catch(() => {});
+    }
+  }
+
+  // Warm the main process's palette cache so the next tracks theme instantly
+  const PALETTE_PREFETCH_COUNT = 3;
+
+  function prefetchUpcomingPalettes(mk: MusicKit.MusicKitInstance): void {
+    const index = (mk as any).nowPlayingItemIndex ?? -1;
+    const urls = state().queue
+      .slice(index + 1, index + 1 + PALETTE_PREFETCH_COUNT)
+      .map((item) => item.attributes?.artwork)
+      .filter((artwork): artwork is MusicKit.Artwork => !!artwork)
+      .map((artwork) => formatArtworkUrl(artwork, 512));
+    if (urls.length > 0) {
+      window.electron.prefetchPalettes(urls).catch(() => {});
+    }
+  }
+
   // Flag to skip the next queueItemsDidChange event (after manual remove/reorder)
   let skipNextQueueSync = false;
 
@@ -564,8 +665,9 @@ function createPlayerStore(): PlayerStore {
       const mk = musicKitStore.instance() as any;
       if (!mk) return;
       const items = mk.queue?.items || [];
-      const validItems = Array.from(items).filter((item: any) => item != null);
+      const validItems = Array.from(items).filter((item: any) => item != null) as MusicKit.MediaItem[];
       setState((prev) => ({ ...prev, queue: validItems }));
+      reportQueue({ kind: 'replaced', items: validItems.map(toQueueSummary) });
     }, 50);
   }
 
@@ -631,6 +733,7 @@ function createPlayerStore(): PlayerStore {
       nowPlaying: null,
       isPlaying: false,
     }));
+    reportQueue({ kind: 'cleared' });
     setCurrentTime(0);
     setDuration(0);
   }
@@ -646,6 +749,7 @@ function createPlayerStore(): PlayerStore {
     newQueue.splice(index, 1);
     skipNextQueueSync = true;
     setState((prev) => ({ ...prev, queue: newQueue }));
+    reportQueue({ kind: 'removed', indices: [index] });
 
     if (mk.queue && typeof mk.queue.remove === 'function') {
       try {
@@ -669,15 +773,17 @@ function createPlayerStore(): PlayerStore {
 
     skipNextQueueSync = true;
     setState((prev) => ({ ...prev, queue: newQueue }));
+    reportQueue({ kind: 'moved', from: fromIndex, to: toIndex });
     console.log('[Player] Queue reordered (local state)');
   }
 
   async function restoreLastPlayed(mk: MusicKit.MusicKitInstance): Promise<void> {
     try {
       const history = await storageService.getPlayHistory();
-      if (history.length === 0) return;
+      // Imported entries have no catalog id to queue
+      const last = history.find((entry) => entry.type !== IMPORTED_HISTORY_TYPE);
+      if (!last) return;
 
-      const last = history[0];
       const queueKey = last.type.startsWith('library') ? 'songs' : 'song';
       await mk.setQueue({ [queueKey]: last.id });
       // setQueue populates nowPlayingItem without starting playback
@@ -1,8 +1,117 @@
+interface LibrarySnapshot {
+  items: Array<{ id: string; type: 'album' | 'playlist'; title: string; artworkUrl?: string }>;
+  updated_at: number;
+}
+
+interface QueueItemSummary {
+  id: string;
+  type: string;
+  title?: string;
+  artist?: string;
+}
+
+type QueueUpdate =
+  | { kind: 'added'; items: QueueItemSummary[]; at: number }
+  | { kind: 'removed'; indices: number[] }
+  | { kind: 'moved'; from: number; to: number }
+  | { kind: 'cleared' }
+  | { kind: 'replaced'; items: QueueItemSummary[] };
+
+interface MainPlayerState {
+  trackId: string | null;
+  positionMs: number;
+  durationMs: number;
+  isPlaying: boolean;
+  // Filled in by main from the persisted playback modes
+  shuffle: boolean;
+  repeat: 'off' | 'one' | 'all';
+}
+
+type MiniPlayerArtAction = 'openMain' | 'openLyrics' | 'none';
+
+// Structured error from commands registered with handleCommand() in main
+interface AppError {
+  code: string;
+  message: string;
+  details?: Record<string, unknown>;
+}
+
+// One song's most recent play from an Apple Music privacy export
+interface ExportedPlay {
+  played_at: number;
+  track: string;
+  artist: string;
+  ms_played: number;
+  source: 'apple-export';
+}
+
+// Secret-free view of the developer token (no signature, no key)
+interface AuthDebugTrace {
+  source: 'runtime-key' | 'build-time';
+  cached: boolean;
+  header: Record<string, unknown>;
+  claims: Record<string, unknown>;
+  ttl_seconds: number | null;
+}
+
+interface CacheUsage {
+  budget_bytes: number;
+  total_bytes: number;
+  caches: Record<string, number>;
+}
+
+interface Palette {
+  dominant: string;
+  secondary: string;
+}
+
+type ArtworkQuality = 'low' | 'medium' | 'high';
+
+type MediaCommand =
+  | { type: 'Seek'; position_ms: number }
+  | { type: 'PlayPause' }
+  | { type: 'Next' }
+  | { type: 'Previous' };
+
+interface ProcessStats {
+  rss_bytes: number;
+  cpu_percent: number;
+  thread_count: number | null;
+}
+
+type ContentRatingFilter = 'explicit' | 'clean';
+
+interface MusicKitInitConfig {
+  developerToken: string;
+  app: { name: string; build: string };
+  contentRating: ContentRatingFilter;
+}
+
 interface ElectronAPI {
   // Token
   getDeveloperToken(): Promise<string>;
   refreshDeveloperToken(): Promise<string>;
   isMusicKitConfigured(): Promise<boolean>;
+  testDeveloperToken(): Promise<boolean>;
+  getSigningKeyFingerprint(): Promise<{ fingerprint: string; key_id: string }>;
+  getDeveloperTokenExpiry(): Promise<{ exp: number | null; remaining_seconds: number | null }>;
+  rotatePrivateKey(keyContent: string, keyId: string): Promise<{ persisted: boolean }>;
+  // Rejects with KEY_STORAGE_UNAVAILABLE without OS encryption unless allowPlaintext
+  setMusicKitConfig(teamId: string, keyId: string, keyContent: string, allowPlaintext?: boolean): Promise<void>;
+  getAuthDebugEnabled(): Promise<boolean>;
+  setAuthDebugEnabled(enabled: boolean): Promise<boolean>;
+  getAuthDebugTrace(): Promise<AuthDebugTrace>;
+  onDeveloperTokenRefreshed(callback: (token: string) => void): () => void;
+  onDeveloperTokenExpired(callback: (event: { exp: number | null }) => void): () => void;
+
+  // MusicKit
+  getMusicKitInitConfig(): Promise<MusicKitInitConfig>;
+  setContentRatingFilter(mode: ContentRatingFilter): Promise<ContentRatingFilter>;
+  onContentFilterChanged(callback: (mode: ContentRatingFilter) => void): () => void;
+
+  // API
+  getApiBaseUrl(): Promise<string>;
+  setApiBaseUrl(url: string): Promise<string>;
 
   // Auth
   openAuthWindow(authUrl: string): Promise<void>;
@@ -10,6 +119,13 @@ interface ElectronAPI {
   // Windows
   openMiniPlayer(): Promise<void>;
   closeMiniPlayer(): Promise<void>;
+  getMiniPlayerArtAction(): Promise<MiniPlayerArtAction>;
+  setMiniPlayerArtAction(action: MiniPlayerArtAction): Promise<MiniPlayerArtAction>;
+  miniPlayerArtClicked(): Promise<MiniPlayerArtAction>;
+  setMiniPlayerFullscreenBehavior(showOverFullscreen: boolean): Promise<void>;
+  setWindowMinSize(label: string, width: number, height: number): Promise<{ width: number; height: number }>;
+  setTrafficLightInset(label: string, x: number, y: number): Promise<{ x: number; y: number }>;
+  frontendReady(): Promise<void>;
   hideMainWindow(): Promise<void>;
   showMainWindow(): Promise<void>;
 
@@ -17,9 +133,11 @@ interface ElectronAPI {
   setCloseBehavior(miniPlayer: boolean): Promise<void>;
   setOpenAtLogin(value: boolean): Promise<void>;
   getOpenAtLogin(): Promise<boolean>;
+  getLanguage(): Promise<string>;
+  setLanguage(code: string | null): Promise<string>;
 
   // Discord
-  discordConnect(): Promise<void>;
+  discordConnect(appId: string): Promise<void>;
   discordDisconnect(): Promise<void>;
   discordSetActivity(params: {
     details: string;
@@ -30,17 +148,136 @@ interface ElectronAPI {
     smallImageText?: string;
     startTimestamp?: number;
     endTimestamp?: number;
+    isPaused?: boolean;
+    trackUrl?: string;
   }): Promise<void>;
   discordClearActivity(): Promise<void>;
+  getDiscordPauseGrace(): Promise<number>;
+  setDiscordPauseGrace(seconds: number): Promise<number>;
+  getDiscordShowWhilePaused(): Promise<boolean>;
+  setDiscordShowWhilePaused(enabled: boolean): Promise<boolean>;
+
+  // Setup
+  completeSetup(): Promise<void>;
+
+  // Events
+  replayEvents(): Promise<Record<string, unknown>>;
+
+  // Health
+  startHealthServer(port: number): Promise<number>;
+  stopHealthServer(): Promise<void>;
+
+  // Remote Control
+  startRemoteControl(port?: number): Promise<{ code: string; url: string }>;
+  stopRemoteControl(): Promise<void>;
+
+  // Branding
+  getBranding(): Promise<{
+    appName: string;
+    accentColor: string;
+    trayTooltip: string;
+    iconPath: string | null;
+  }>;
+
+  // Playback
+  checkPlaybackPrerequisites(): Promise<{
+    eme_available: boolean;
+    webkit_version: string;
+    notes: string[];
+  }>;
+  setPlayerState(state: Omit<MainPlayerState, 'shuffle' | 'repeat'>): Promise<void>;
+  getPlayerState(): Promise<MainPlayerState>;
+  requestSeek(positionMs: number): Promise<number>;
+  onMediaCommand(callback: (command: MediaCommand) => void): () => void;
+  getPlaybackSnapshot(): Promise<{ trackId: string; positionMs: number; savedAt: number } | null>;
+  setPositionSaveInterval(seconds: number): Promise<number>;
+  applyQueueUpdate(update: QueueUpdate): Promise<void>;
+  getQueue(): Promise<QueueItemSummary[]>;
+  onQueueUpdate(callback: (update: QueueUpdate) => void): () => void;
+  setTrackDurations(durations: Record<string, number>): Promise<void>;
+  getCrossfadeWindow(trackId: string): Promise<{ enabled: boolean; start_ms: number | null }>;
+  onCrossfadeStart(callback: (event: { next_index: number | null }) => void): () => void;
+  onTrackNearEnd(callback: (event: { track_id: string; remaining_ms: number }) => void): () => void;
+  getCrossfadeSeconds(): Promise<number>;
+  setCrossfadeSeconds(seconds: number): Promise<number>;
+  scrobbleEligibility(trackDurationMs: number, positionMs: number): Promise<{ eligible: boolean; at_ms: number | null }>;
+  getScrobbleFullOnly(): Promise<boolean>;
+  setScrobbleFullOnly(enabled: boolean): Promise<boolean>;
+  getAutoplay(): Promise<boolean>;
+  setAutoplay(enabled: boolean): Promise<boolean>;
+  reportQueueEnded(lastTrackId: string | null): Promise<boolean>;
+  onAutoplayChanged(callback: (enabled: boolean) => void): () => void;
+  onRequestAutoplaySeed(callback: (seed: { last_track_id: string }) => void): () => void;
+  getPlaybackModes(): Promise<{ shuffle: boolean; repeat: 'off' | 'one' | 'all' }>;
+  setPlaybackModes(shuffle: boolean, repeat: 'off' | 'one' | 'all'): Promise<{ shuffle: boolean; repeat: 'off' | 'one' | 'all' }>;
+  onPlaybackModesRestored(callback: (modes: { shuffle: boolean; repeat: 'off' | 'one' | 'all' }) => void): () => void;
+
+  // Artwork
+  getCachedArtwork(url: string | null, size?: number): Promise<string>;
+  resolveArtworkUrl(url: string, size?: number): Promise<string>;
+  getArtworkQuality(): Promise<{ preferred: ArtworkQuality; effective: ArtworkQuality }>;
+  setArtworkQuality(quality: ArtworkQuality): Promise<ArtworkQuality>;
+  setNetworkMetered(metered: boolean): Promise<void>;
+  onArtworkQualityChanged(callback: (quality: ArtworkQuality) => void): () => void;
+  setFallbackArtwork(path: string | null): Promise<string | null>;
+  repairArtworkCache(): Promise<{ removed: number; reindexed: number; total_bytes: number }>;
+  cacheUsage(): Promise<CacheUsage>;
+  setCacheBudget(bytes: number): Promise<CacheUsage>;
+  prefetchPalettes(urls: string[]): Promise<number>;
+  setActiveArtwork(url: string): Promise<Palette>;
+  getActivePalette(): Promise<Palette | null>;
+
+  // Library
+  cacheLibrarySnapshot(
+    items: Array<{ id: string; type: 'album' | 'playlist'; title: string; artworkUrl?: string }>,
+  ): Promise<LibrarySnapshot>;
+  getLibrarySnapshot(): Promise<LibrarySnapshot | null>;
+  importAppleMusicExport(path: string): Promise<{ entries: ExportedPlay[]; skipped: number }>;
+
+  // Audio Output
+  listOutputDevices(): Promise<{
+    devices: Array<{ id: string; label: string }>;
+    currentDeviceId: string | null;
+  }>;
+  reportOutputDevices(devices: Array<{ id: string; label: string }>, currentId: string | null): Promise<void>;
+  onSetOutputDevice(callback: (deviceId: string) => void): () => void;
+  onAudioRouteChanged(
+    callback: (route: { devices: Array<{ id: string; label: string }>; current_device_id: string | null }) => void,
+  ): () => void;
+
+  // Recap
+  generateListeningRecap(
+    trackArts: Array<{ url: string; title: string; artist: string }>,
+    title: string,
+  ): Promise<string>;
+
+  // Performance
+  getProcessStats(): Promise<ProcessStats>;
+  setPerfOverlay(enabled: boolean): Promise<void>;
+  onProcessStats(callback: (stats: ProcessStats) => void): () => void;
+
+  // Telemetry
+  setTelemetry(enabled: boolean, endpoint?: string): Promise<{ enabled: boolean; endpoint: string | null; pending: number }>;
+  getTelemetryStatus(): Promise<{ enabled: boolean; endpoint: string | null; pending: number }>;
+  recordTelemetryEvent(name: string, count?: number): Promise<void>;
+
+  // Logging
+  frontendLog(level: string, message: string, context?: unknown): Promise<void>;
 
   // Global Shortcuts
   registerShortcut(accelerator: string): Promise<boolean>;
   unregisterShortcut(accelerator: string): Promise<void>;
+  setShortcuts(bindings: Record<string, string>): Promise<{ failed: string[] }>;
+  getEffectiveShortcuts(): Promise<Record<string, { accelerator: string; is_default: boolean; registered: boolean }>>;
   unregisterAllShortcuts(): Promise<void>;
+  getMediaKeysEnabled(): Promise<boolean>;
+  setMediaKeysEnabled(enabled: boolean): Promise<{ enabled: boolean; failed: string[] }>;
 
   // Mini Player mode events
   onEnterMiniPlayer(callback: () => void): () => void;
   onExitMiniPlayer(callback: () => void): () => void;
+  onOpenLyrics(callback: () => void): () => void;
+  onAudioInterruption(callback: (event: { began: boolean; should_resume: boolean }) => void): () => void;
 
   // Events (return unlisten function)
   onAppleMusicToken(callback: (token: string) => void): () => void;
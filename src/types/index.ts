@@ -14,6 +14,7 @@ export interface AppSettings {
   equalizerPreset: EqualizerPreset;
   equalizerCustom: EqualizerBands;
   discordRichPresence: boolean;
+  discordAppId: string;
   sleepTimer: SleepTimerSettings;
   keyboardShortcutsEnabled: boolean;
   customKeyBindings: Record<string, string>;
@@ -228,6 +229,8 @@ export interface DiscordPresence {
   smallImageText?: string;
   startTimestamp?: number;
   endTimestamp?: number;
+  isPaused?: boolean;
+  trackUrl?: string;
 }
 
 // Keyboard Shortcuts